@@ -0,0 +1,176 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::time::Instant;
+use std::{cell::RefCell, rc::Rc};
+
+use crate::node::FindMovableNodes;
+use crate::{Direction, Goal, Heuristic, Node, SearchLimits, SearchStats, Solve, SolveError, SolvedState, TieBreaking, TOTAL_PUZZLE_SIZE};
+
+pub struct AStar<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    heuristic: Heuristic,
+    tie_breaking: TieBreaking,
+    limits: SearchLimits,
+    ending_node: Option<Node<N>>,
+    pub is_ending_node_reached: bool,
+}
+
+impl<const N: usize> AStar<N> {
+    pub fn new() -> AStar<N> {
+        AStar::with_goal(Goal::default())
+    }
+
+    /// Creates a solver that works towards the given goal instead of the default one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algo::{AStar, Board, Goal, Solve};
+    ///
+    /// let mut solver = AStar::with_goal(Goal::blank_first(Board::square(3)));
+    /// let solved_state = solver.solve(&mut [0, 8, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+    ///
+    /// assert_eq!(solved_state.moves, vec![algo::Direction::Left]);
+    /// ```
+    pub fn with_goal(goal: Goal<N>) -> AStar<N> {
+        AStar {
+            goal,
+            heuristic: Heuristic::default(),
+            tie_breaking: TieBreaking::default(),
+            limits: SearchLimits::none(),
+            ending_node: Option::None,
+            is_ending_node_reached: false
+        }
+    }
+
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> AStar<N> {
+        self.heuristic = heuristic;
+        self
+    }
+
+    /// Decides which of the open nodes with the lowest value is expanded first.
+    pub fn with_tie_breaking(mut self, tie_breaking: TieBreaking) -> AStar<N> {
+        self.tie_breaking = tie_breaking;
+        self
+    }
+
+    pub fn with_limits(mut self, limits: SearchLimits) -> AStar<N> {
+        self.limits = limits;
+        self
+    }
+}
+
+impl<const N: usize> Default for AStar<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A node in the open list, ordered so the `BinaryHeap` pops the lowest value first
+/// and, between equal values, the highest key of the `TieBreaking`.
+struct OpenNode<const N: usize> {
+    value: i32,
+    tie_key: (i64, i64),
+    node: Rc<RefCell<Node<N>>>,
+}
+
+impl<const N: usize> PartialEq for OpenNode<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<const N: usize> Eq for OpenNode<N> {}
+
+impl<const N: usize> PartialOrd for OpenNode<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for OpenNode<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.value.cmp(&self.value).then(self.tie_key.cmp(&other.tie_key))
+    }
+}
+
+impl<const N: usize> Solve<N> for AStar<N> {
+    fn solve(&mut self, puzzle_state: &mut [i8; N]) -> Result<SolvedState<N>, SolveError> {
+        if !self.goal.is_reachable_from(puzzle_state) {
+            return Err(SolveError::Unsolvable);
+        }
+
+        let started: Instant = Instant::now();
+        let mut stats: SearchStats = SearchStats::default();
+        // The open list is a priority queue on the node value, states that are reached again with a shorter
+        // length are pushed again, leaving the older entry behind to be skipped when it is popped.
+        let mut open_list: BinaryHeap<OpenNode<N>> = BinaryHeap::new();
+        // The shortest length every opened or closed state has been reached with.
+        let mut lengths: HashMap<[i8; N], i32> = HashMap::new();
+        let mut sequence: u64 = 0;
+
+        let starting_node: Node<N> = Node::new(*puzzle_state, &self.goal, &self.heuristic);
+        self.is_ending_node_reached = false;
+        self.ending_node = None;
+        lengths.insert(starting_node.puzzle_state, 0);
+        let tie_key: (i64, i64) = self.tie_breaking.key(starting_node.length, starting_node.distance, sequence);
+        open_list.push(OpenNode { value: starting_node.value, tie_key, node: Rc::new(RefCell::new(starting_node)) });
+
+        while !self.is_ending_node_reached {
+            let Some(OpenNode { node: min_value_node, .. }) = open_list.pop() else {
+                return Err(SolveError::Unsolvable);
+            };
+            {
+                let node = min_value_node.as_ref().borrow();
+                if lengths[&node.puzzle_state] < node.length {
+                    continue;
+                }
+                if node.is_ending_node {
+                    self.is_ending_node_reached = true;
+                    self.ending_node = Some(node.clone());
+                    break;
+                }
+            }
+
+            if self.limits.is_reached(&stats, started) {
+                stats.elapsed = started.elapsed();
+                return Err(SolveError::LimitReached(stats));
+            }
+            stats.expanded += 1;
+
+            for node in min_value_node.get_possible_nodes(&self.goal, &self.heuristic).into_iter() {
+                stats.generated += 1;
+                if lengths.get(&node.puzzle_state).is_some_and(|length| *length <= node.length) {
+                    continue;
+                }
+                lengths.insert(node.puzzle_state, node.length);
+                sequence += 1;
+                let tie_key: (i64, i64) = self.tie_breaking.key(node.length, node.distance, sequence);
+                open_list.push(OpenNode { value: node.value, tie_key, node: Rc::new(RefCell::new(node)) });
+            }
+        }
+
+        let final_puzzle_state: [i8; N] = self.ending_node.as_ref().unwrap().puzzle_state;
+        let mut node = Some(Rc::new(RefCell::new(self.ending_node.as_ref().unwrap().clone())));
+        let mut moves: Vec<Direction> = Vec::new();
+
+        '_move_loop: while node.as_ref().unwrap().as_ref().borrow().parent_node.is_some() {
+            node = {
+                let node_ = node.as_ref().unwrap().as_ref().borrow();
+                moves.push(node_.direction);
+                if node_.parent_node.is_none() {
+                    break '_move_loop;
+                }
+                node_.parent_node.clone()
+            };
+        }
+        moves.reverse();
+        stats.elapsed = started.elapsed();
+
+        Ok(SolvedState {
+            puzzle_state: final_puzzle_state,
+            moves,
+            stats,
+        })
+    }
+}
@@ -0,0 +1,117 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{Solve, SolveError, SolvedState};
+
+/// Spreads independent jobs, like solving the instances of a batch, over a pool of worker threads.
+///
+/// The solvers keep their nodes in `Rc<RefCell<Node>>` and can't move between threads,
+/// so every worker builds the solvers it needs itself.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub struct BatchRunner {
+    workers: usize,
+}
+
+/// The results of a batch in the order of its input, with the time the whole batch took.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct BatchResults<R> {
+    pub results: Vec<R>,
+    pub workers: usize,
+    pub elapsed: Duration,
+}
+
+impl<R> BatchResults<R> {
+    /// Gets the number of jobs finished per second of wall time.
+    pub fn throughput(&self) -> f64 {
+        self.results.len() as f64 / self.elapsed.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+}
+
+impl BatchRunner {
+    /// # Panics
+    ///
+    /// Panics when there are no workers.
+    pub fn new(workers: usize) -> BatchRunner {
+        assert!(workers > 0, "a batch needs at least one worker");
+        BatchRunner { workers }
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Runs the job on every item, handing out the next item to whichever worker is free.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let squares = algo::BatchRunner::new(3).run(&[1, 2, 3, 4], |number: &i32| number * number);
+    ///
+    /// assert_eq!(squares.results, vec![1, 4, 9, 16]);
+    /// ```
+    pub fn run<T, R, F>(&self, items: &[T], job: F) -> BatchResults<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync,
+    {
+        let started: Instant = Instant::now();
+        let next_item: AtomicUsize = AtomicUsize::new(0);
+        let finished: Mutex<Vec<Option<R>>> = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<_>>());
+        let workers: usize = self.workers.min(items.len()).max(1);
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let index: usize = next_item.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    let result: R = job(item);
+                    finished.lock().unwrap()[index] = Some(result);
+                });
+            }
+        });
+
+        BatchResults {
+            results: finished.into_inner().unwrap().into_iter().map(|result| result.expect("every item was run")).collect::<Vec<_>>(),
+            workers,
+            elapsed: started.elapsed(),
+        }
+    }
+
+    /// Solves every puzzle state with a fresh solver from `create_solver`, which is called on the worker
+    /// threads. Limits set on the solvers apply to every instance on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algo::{AStar, BatchRunner};
+    ///
+    /// let puzzle_states: Vec<[i8; 9]> = vec![[0, 1, 2, 3, 4, 5, 6, 8, 7], [0, 1, 2, 3, 4, 8, 6, 7, 5]];
+    /// let batch = BatchRunner::new(2).solve(&puzzle_states, AStar::new);
+    ///
+    /// assert_eq!(batch.results[1].as_ref().unwrap().moves, vec![algo::Direction::Down]);
+    /// ```
+    pub fn solve<const N: usize, S, F>(&self, puzzle_states: &[[i8; N]], create_solver: F) -> BatchResults<Result<SolvedState<N>, SolveError>>
+    where
+        S: Solve<N>,
+        F: Fn() -> S + Sync,
+    {
+        self.run(puzzle_states, |puzzle_state| create_solver().solve(&mut puzzle_state.clone()))
+    }
+}
+
+impl Default for BatchRunner {
+    /// A worker for every thread the machine can run at once.
+    fn default() -> Self {
+        BatchRunner::new(thread::available_parallelism().map_or(1, NonZeroUsize::get))
+    }
+}
@@ -0,0 +1,215 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::time::Instant;
+
+use crate::node::movable_piece_location;
+use crate::{invert_moves, AStar, Direction, Goal, Heuristic, SearchLimits, SearchStats, Solve, SolveError, SolvedState, TOTAL_PUZZLE_SIZE};
+
+/// Bidirectional A* with the stopping rule of MM, which searches forward from the puzzle state and
+/// backward from the goal until the two searches meet.
+///
+/// Both searches expand the node with the lowest priority `max(f, 2g)`, which keeps each of them
+/// from passing the middle of the shortest path. Every time a search reaches a state the other
+/// one has seen, the two halves form a path. Once no open node has a priority below the shortest
+/// of those paths, no shorter one can exist and that path is returned.
+///
+/// The backward search needs a single goal state, so partial goals are searched forward with `AStar`.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{BidirectionalAStar, Heuristic, Solve};
+///
+/// let mut solver: BidirectionalAStar = BidirectionalAStar::new().with_heuristic(Heuristic::LinearConflict);
+/// let solved_state = solver.solve(&mut [5, 2, 3, 1, 8, 4, 6, 0, 7]).unwrap();
+///
+/// assert_eq!(solved_state.moves.len(), 22);
+/// ```
+pub struct BidirectionalAStar<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    heuristic: Heuristic,
+    limits: SearchLimits,
+}
+
+/// How one of the searches reached a state for the shortest length so far.
+struct Entry<const N: usize> {
+    length: i32,
+    parent: Option<[i8; N]>,
+    direction: Direction,
+}
+
+/// A node in the open list of one of the searches, popping the lowest priority first.
+#[derive(PartialEq, Eq)]
+struct OpenNode<const N: usize> {
+    priority: i32,
+    length: i32,
+    puzzle_state: [i8; N],
+}
+
+impl<const N: usize> Ord for OpenNode<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority).then(other.length.cmp(&self.length)).then(self.puzzle_state.cmp(&other.puzzle_state))
+    }
+}
+
+impl<const N: usize> PartialOrd for OpenNode<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// One direction of the search, working towards its own goal.
+struct Frontier<const N: usize> {
+    goal: Goal<N>,
+    open_list: BinaryHeap<OpenNode<N>>,
+    entries: HashMap<[i8; N], Entry<N>>,
+}
+
+impl<const N: usize> Frontier<N> {
+    fn new(start: [i8; N], goal: Goal<N>, heuristic: &Heuristic) -> Frontier<N> {
+        let mut frontier: Frontier<N> = Frontier { goal, open_list: BinaryHeap::new(), entries: HashMap::new() };
+        frontier.open(start, 0, None, Direction::None, heuristic);
+        frontier
+    }
+
+    /// Opens the state unless it was already reached with at most the same length, telling whether it was opened.
+    fn open(&mut self, puzzle_state: [i8; N], length: i32, parent: Option<[i8; N]>, direction: Direction, heuristic: &Heuristic) -> bool {
+        if self.entries.get(&puzzle_state).is_some_and(|entry| entry.length <= length) {
+            return false;
+        }
+        let value: i32 = length + heuristic.estimate(&puzzle_state, &self.goal);
+        self.entries.insert(puzzle_state, Entry { length, parent, direction });
+        self.open_list.push(OpenNode { priority: value.max(2 * length), length, puzzle_state });
+        true
+    }
+
+    /// Gets the lowest priority in the open list, dropping the nodes that were reached again with a shorter length.
+    fn min_priority(&mut self) -> Option<i32> {
+        while let Some(node) = self.open_list.peek() {
+            if self.entries[&node.puzzle_state].length == node.length {
+                return Some(node.priority);
+            }
+            self.open_list.pop();
+        }
+        None
+    }
+
+    /// Gets the moves from the start of this search to the given state.
+    fn moves_to(&self, puzzle_state: &[i8; N]) -> Vec<Direction> {
+        let mut moves: Vec<Direction> = Vec::new();
+        let mut state: [i8; N] = *puzzle_state;
+        while let Some(parent) = self.entries[&state].parent {
+            moves.push(self.entries[&state].direction);
+            state = parent;
+        }
+        moves.reverse();
+        moves
+    }
+}
+
+impl<const N: usize> BidirectionalAStar<N> {
+    pub fn new() -> BidirectionalAStar<N> {
+        BidirectionalAStar::with_goal(Goal::default())
+    }
+
+    pub fn with_goal(goal: Goal<N>) -> BidirectionalAStar<N> {
+        BidirectionalAStar {
+            goal,
+            heuristic: Heuristic::default(),
+            limits: SearchLimits::none(),
+        }
+    }
+
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> BidirectionalAStar<N> {
+        self.heuristic = heuristic;
+        self
+    }
+
+    pub fn with_limits(mut self, limits: SearchLimits) -> BidirectionalAStar<N> {
+        self.limits = limits;
+        self
+    }
+}
+
+impl<const N: usize> Default for BidirectionalAStar<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Solve<N> for BidirectionalAStar<N> {
+    fn solve(&mut self, puzzle_state: &mut [i8; N]) -> Result<SolvedState<N>, SolveError> {
+        if self.goal.is_partial() {
+            return AStar::with_goal(self.goal.clone()).with_heuristic(self.heuristic).with_limits(self.limits.clone()).solve(puzzle_state);
+        }
+        if !self.goal.is_reachable_from(puzzle_state) {
+            return Err(SolveError::Unsolvable);
+        }
+
+        let started: Instant = Instant::now();
+        let mut stats: SearchStats = SearchStats::default();
+        let board = *self.goal.board();
+        let start_goal: Goal<N> = Goal::new(board, *puzzle_state).expect("a puzzle state places every tile once");
+        let mut forward: Frontier<N> = Frontier::new(*puzzle_state, self.goal.clone(), &self.heuristic);
+        let mut backward: Frontier<N> = Frontier::new(*self.goal.puzzle_state(), start_goal, &self.heuristic);
+
+        // the length of the shortest path found so far and the state where its halves meet
+        let mut best: Option<(i32, [i8; N])> = match forward.entries.contains_key(self.goal.puzzle_state()) {
+            true => Some((0, *puzzle_state)),
+            false => None,
+        };
+
+        while let (Some(forward_priority), Some(backward_priority)) = (forward.min_priority(), backward.min_priority()) {
+            if best.is_some_and(|(length, _)| length <= forward_priority.min(backward_priority)) {
+                break;
+            }
+            if self.limits.is_reached(&stats, started) {
+                stats.elapsed = started.elapsed();
+                return Err(SolveError::LimitReached(stats));
+            }
+
+            let (frontier, other) = match forward_priority <= backward_priority {
+                true => (&mut forward, &backward),
+                false => (&mut backward, &forward),
+            };
+            let node: OpenNode<N> = frontier.open_list.pop().unwrap();
+            stats.expanded += 1;
+
+            let previous_direction: Direction = frontier.entries[&node.puzzle_state].direction;
+            let movable_piece_location: usize = movable_piece_location(&node.puzzle_state);
+            for direction in Direction::MOVES {
+                if direction == previous_direction.opposite() {
+                    continue;
+                }
+                let Some(target) = board.neighbour(movable_piece_location, direction) else {
+                    continue;
+                };
+                stats.generated += 1;
+                let mut child: [i8; N] = node.puzzle_state;
+                child.swap(movable_piece_location, target);
+                if !frontier.open(child, node.length + 1, Some(node.puzzle_state), direction, &self.heuristic) {
+                    continue;
+                }
+                if let Some(entry) = other.entries.get(&child) {
+                    let length: i32 = node.length + 1 + entry.length;
+                    if best.is_none_or(|(best_length, _)| length < best_length) {
+                        best = Some((length, child));
+                    }
+                }
+            }
+        }
+
+        let Some((_, meeting_state)) = best else {
+            return Err(SolveError::Unsolvable);
+        };
+        let mut moves: Vec<Direction> = forward.moves_to(&meeting_state);
+        moves.extend(invert_moves(&backward.moves_to(&meeting_state)));
+        stats.elapsed = started.elapsed();
+
+        Ok(SolvedState {
+            puzzle_state: *self.goal.puzzle_state(),
+            moves,
+            stats,
+        })
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::VecDeque;
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Direction, PUZZLE_SIZE};
+
+/// How the edges of a board connect.
+#[derive(Debug)]
+#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Topology {
+    /// A rectangle, moves stop at the edges.
+    #[default]
+    Flat,
+    /// Moves off the left or right edge come back on the other side.
+    Cylinder,
+    /// Moves off any edge come back on the opposite side.
+    Torus,
+}
+
+impl Topology {
+    pub const ALL: [Topology; 3] = [Topology::Flat, Topology::Cylinder, Topology::Torus];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Topology::Flat => "flat",
+            Topology::Cylinder => "cylinder",
+            Topology::Torus => "torus",
+        }
+    }
+
+    pub fn wraps_horizontally(&self) -> bool {
+        *self != Topology::Flat
+    }
+
+    pub fn wraps_vertically(&self) -> bool {
+        *self == Topology::Torus
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Topology {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Topology, String> {
+        Topology::ALL
+            .into_iter()
+            .find(|topology| topology.name() == text)
+            .ok_or_else(|| format!("unknown topology '{}', expected one of flat, cylinder or torus", text))
+    }
+}
+
+/// The shape of the puzzle, positions are numbered in reading order from `0` to `width * height - 1`.
+///
+/// A board can wrap around its edges and can have blocked positions, which no move enters.
+/// The tile on a blocked position never moves, so it has to be in its goal position from the start.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Board, Direction, Topology};
+///
+/// let board: Board = "3x3:torus".parse().unwrap();
+/// assert_eq!(board.neighbour(0, Direction::Left), Some(2));
+/// assert_eq!(board.distance(0, 8), 2);
+///
+/// let board: Board = Board::square(3).with_blocked(&[4]);
+/// assert_eq!(board.neighbour(1, Direction::Down), None);
+/// assert_eq!(board.to_string(), "3x3:flat:4");
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, Copy)]
+#[derive(Serialize, Deserialize)]
+#[serde(try_from = "Dimensions", into = "Dimensions")]
+pub struct Board {
+    width: usize,
+    height: usize,
+    topology: Topology,
+    /// A bit for every blocked position.
+    blocked: u64,
+}
+
+impl Board {
+    /// # Panics
+    ///
+    /// Panics when the board is smaller than 2 by 2, as the tiles of such a board can't be rearranged.
+    pub fn new(width: usize, height: usize) -> Board {
+        assert!(width >= 2 && height >= 2, "a board needs at least 2 rows and 2 columns, got {}x{}", width, height);
+        Board { width, height, topology: Topology::Flat, blocked: 0 }
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Board {
+        self.topology = topology;
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics when a position is outside of the board or beyond the first 64 positions.
+    pub fn with_blocked(mut self, positions: &[usize]) -> Board {
+        for position in positions {
+            assert!(*position < self.len() && *position < 64, "position {} can't be blocked on a {}x{} board", position, self.width, self.height);
+            self.blocked |= 1 << position;
+        }
+        self
+    }
+
+    pub fn square(size: usize) -> Board {
+        Board::new(size, size)
+    }
+
+    /// Gets the square board that has the given number of positions.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the number of positions isn't a square.
+    pub fn square_of(len: usize) -> Board {
+        let size: usize = (1..=len).find(|size| size * size >= len).unwrap_or(0);
+        assert_eq!(size * size, len, "{} positions don't form a square board", len);
+        Board::square(size)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets the number of positions on the board.
+    pub fn len(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn is_blocked(&self, position: usize) -> bool {
+        position < 64 && self.blocked & (1 << position) != 0
+    }
+
+    /// Gets the blocked positions in increasing order.
+    pub fn blocked_positions(&self) -> Vec<usize> {
+        (0..self.len().min(64)).filter(|position| self.is_blocked(*position)).collect::<Vec<_>>()
+    }
+
+    pub fn row(&self, position: usize) -> usize {
+        position / self.width
+    }
+
+    pub fn column(&self, position: usize) -> usize {
+        position % self.width
+    }
+
+    /// Gets the position reached when moving from `position` in the given direction,
+    /// or `None` when that leaves a flat edge or enters a blocked position.
+    pub fn neighbour(&self, position: usize, direction: Direction) -> Option<usize> {
+        let column: usize = self.column(position);
+        let row: usize = self.row(position);
+        let (wraps_horizontally, wraps_vertically) = (self.topology.wraps_horizontally(), self.topology.wraps_vertically());
+        let target: Option<usize> = match direction {
+            Direction::Left if column > 0 => Some(position - 1),
+            Direction::Left if wraps_horizontally => Some(position + self.width - 1),
+            Direction::Right if column + 1 < self.width => Some(position + 1),
+            Direction::Right if wraps_horizontally => Some(position + 1 - self.width),
+            Direction::Up if row > 0 => Some(position - self.width),
+            Direction::Up if wraps_vertically => Some(position + self.len() - self.width),
+            Direction::Down if row + 1 < self.height => Some(position + self.width),
+            Direction::Down if wraps_vertically => Some(position + self.width - self.len()),
+            _ => None,
+        };
+        target.filter(|target| !self.is_blocked(*target))
+    }
+
+    /// Gets the number of horizontal and vertical steps between two positions, going around the
+    /// edges that wrap when that is shorter. Blocked positions can make the way longer.
+    pub fn distance(&self, a: usize, b: usize) -> usize {
+        let mut columns: usize = self.column(a).abs_diff(self.column(b));
+        if self.topology.wraps_horizontally() {
+            columns = columns.min(self.width - columns);
+        }
+        let mut rows: usize = self.row(a).abs_diff(self.row(b));
+        if self.topology.wraps_vertically() {
+            rows = rows.min(self.height - rows);
+        }
+        columns + rows
+    }
+
+    /// Gets the number of moves the movable piece needs from the position to every position,
+    /// or `None` for the positions it can't reach.
+    pub fn steps_from(&self, position: usize) -> Vec<Option<usize>> {
+        let mut steps: Vec<Option<usize>> = vec![None; self.len()];
+        let mut queue: VecDeque<usize> = VecDeque::from([position]);
+        steps[position] = Some(0);
+        while let Some(position) = queue.pop_front() {
+            for direction in Direction::MOVES {
+                if let Some(target) = self.neighbour(position, direction) {
+                    if steps[target].is_none() {
+                        steps[target] = Some(steps[position].unwrap() + 1);
+                        queue.push_back(target);
+                    }
+                }
+            }
+        }
+        steps
+    }
+
+    /// Checks whether every cycle the movable piece can go around the positions it reaches
+    /// from the given position has an even length, which is what keeps half of the puzzle states
+    /// out of reach. Cycles of odd length come from wrapping around an odd number of positions.
+    pub fn has_even_cycles(&self, position: usize) -> bool {
+        let steps: Vec<Option<usize>> = self.steps_from(position);
+        (0..self.len()).filter(|position| steps[*position].is_some()).all(|position| {
+            Direction::MOVES
+                .iter()
+                .filter_map(|direction| self.neighbour(position, *direction))
+                .all(|target| steps[target].unwrap() % 2 != steps[position].unwrap() % 2)
+        })
+    }
+
+    /// Checks whether the tiles on the positions the movable piece reaches from the given position
+    /// can pass each other: there are at least three positions, they are more than a single cycle,
+    /// and no position splits them when it is taken out. Wilson's theorem then makes every even
+    /// permutation reachable, and every permutation when a cycle has an odd length. The one exception
+    /// has seven positions joined by eight pairs of neighbours, which is left out with the others of its size.
+    pub fn lets_tiles_pass(&self, position: usize) -> bool {
+        let steps: Vec<Option<usize>> = self.steps_from(position);
+        let region: Vec<usize> = (0..self.len()).filter(|position| steps[*position].is_some()).collect::<Vec<_>>();
+        let mut neighbour_pairs: Vec<(usize, usize)> = region
+            .iter()
+            .flat_map(|position| Direction::MOVES.iter().filter_map(move |direction| self.neighbour(*position, *direction)).map(move |target| (*position.min(&target), *position.max(&target))))
+            .filter(|(a, b)| a != b)
+            .collect::<Vec<_>>();
+        neighbour_pairs.sort();
+        neighbour_pairs.dedup();
+
+        let is_cycle: bool = neighbour_pairs.len() == region.len();
+        let is_exception: bool = region.len() == 7 && neighbour_pairs.len() == 8;
+        if region.len() < 3 || is_cycle || is_exception {
+            return false;
+        }
+        // taking a position out splits the region when the others can't all be reached without it
+        region.iter().all(|removed| {
+            let start: usize = *region.iter().find(|position| *position != removed).unwrap();
+            let mut is_reached: Vec<bool> = vec![false; self.len()];
+            let mut queue: VecDeque<usize> = VecDeque::from([start]);
+            is_reached[start] = true;
+            let mut reached: usize = 1;
+            while let Some(position) = queue.pop_front() {
+                for target in Direction::MOVES.iter().filter_map(|direction| self.neighbour(position, *direction)) {
+                    if target != *removed && !is_reached[target] {
+                        is_reached[target] = true;
+                        reached += 1;
+                        queue.push_back(target);
+                    }
+                }
+            }
+            reached == region.len() - 1
+        })
+    }
+}
+
+/// The fields of a board before they are checked, a flat board without blocked positions is written as before they existed.
+#[derive(Serialize, Deserialize)]
+struct Dimensions {
+    width: usize,
+    height: usize,
+    #[serde(default, skip_serializing_if = "is_flat")]
+    topology: Topology,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    blocked: Vec<usize>,
+}
+
+fn is_flat(topology: &Topology) -> bool {
+    *topology == Topology::Flat
+}
+
+impl TryFrom<Dimensions> for Board {
+    type Error = ParseBoardError;
+
+    fn try_from(dimensions: Dimensions) -> Result<Board, ParseBoardError> {
+        let blocked: String = dimensions.blocked.iter().map(usize::to_string).collect::<Vec<_>>().join(",");
+        format!("{}x{}:{}:{}", dimensions.width, dimensions.height, dimensions.topology, blocked).parse::<Board>()
+    }
+}
+
+impl From<Board> for Dimensions {
+    fn from(board: Board) -> Dimensions {
+        Dimensions {
+            width: board.width,
+            height: board.height,
+            topology: board.topology,
+            blocked: board.blocked_positions(),
+        }
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::square(PUZZLE_SIZE as usize)
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)?;
+        if self.blocked != 0 {
+            let blocked: Vec<String> = self.blocked_positions().iter().map(usize::to_string).collect::<Vec<_>>();
+            write!(f, ":{}:{}", self.topology, blocked.join(","))
+        } else if self.topology != Topology::Flat {
+            write!(f, ":{}", self.topology)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A board written as `WIDTHxHEIGHT` isn't valid.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct ParseBoardError(String);
+
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a board, expected WIDTHxHEIGHT with both at least 2, optionally followed by :flat, :cylinder or :torus and :POSITIONS to block, e.g. 4x4 or 4x4:torus or 4x4:flat:5,6",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseBoardError {}
+
+impl FromStr for Board {
+    type Err = ParseBoardError;
+
+    /// Reads a board written as `WIDTHxHEIGHT`, e.g. `4x4`, optionally followed by the topology
+    /// and the blocked positions in reading order from 0, e.g. `4x4:torus` or `4x4:flat:5,6`.
+    fn from_str(text: &str) -> Result<Board, ParseBoardError> {
+        let error = || ParseBoardError(text.to_string());
+        let mut parts = text.trim().split(':');
+        let (width, height) = parts.next().unwrap().split_once(['x', 'X']).ok_or_else(error)?;
+        let width: usize = width.trim().parse::<usize>().map_err(|_| error())?;
+        let height: usize = height.trim().parse::<usize>().map_err(|_| error())?;
+        if width < 2 || height < 2 {
+            return Err(error());
+        }
+        let topology: Topology = match parts.next() {
+            Some(topology) => topology.trim().parse::<Topology>().map_err(|_| error())?,
+            None => Topology::Flat,
+        };
+        let blocked: Vec<usize> = match parts.next() {
+            Some(blocked) if !blocked.trim().is_empty() => blocked.split(',').map(|position| position.trim().parse::<usize>()).collect::<Result<Vec<_>, _>>().map_err(|_| error())?,
+            _ => Vec::new(),
+        };
+        if parts.next().is_some() || blocked.iter().any(|position| *position >= (width * height).min(64)) {
+            return Err(error());
+        }
+        Ok(Board::new(width, height).with_topology(topology).with_blocked(&blocked))
+    }
+}
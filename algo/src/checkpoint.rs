@@ -0,0 +1,74 @@
+use std::path::Path;
+use std::{fmt, fs, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Board, Direction, Heuristic, SearchStats, TOTAL_PUZZLE_SIZE};
+
+/// Where an iterative deepening search was when it wrote the checkpoint, enough to continue
+/// it after a restart with the same result as an uninterrupted search.
+///
+/// It is written as JSON following `schema`, with the bound and the best value over it
+/// of the iteration, the path to the next node to expand and the work done so far.
+/// The puzzle state, goal, heuristic and pattern are written too, as the search can only resume with those it started with.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct Checkpoint<const N: usize = TOTAL_PUZZLE_SIZE> {
+    /// The puzzle state the search started from.
+    #[serde(with = "crate::schema::puzzle_state")]
+    pub puzzle_state: [i8; N],
+    /// The board of the goal.
+    pub board: Board,
+    /// The puzzle state of the goal, with `WILDCARD` on the positions of a partial goal.
+    #[serde(with = "crate::schema::goal_state")]
+    pub goal: [i8; N],
+    pub heuristic: Heuristic,
+    /// The labels of the pattern tiles of the pattern database the estimates also looked up, empty without one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pattern: Vec<usize>,
+    /// The bound of the iteration.
+    pub bound: i32,
+    /// The lowest value over the bound found in the iteration so far.
+    pub next_bound: Option<i32>,
+    /// The moves to the next node to expand, every node to the left of this path has been searched.
+    #[serde(with = "crate::schema::moves")]
+    pub path: Vec<Direction>,
+    pub stats: SearchStats,
+}
+
+/// Reasons a checkpoint can't be read or written.
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(io::Error),
+    Invalid(serde_json::Error),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(error) => write!(f, "{}", error),
+            CheckpointError::Invalid(error) => write!(f, "not a valid checkpoint, {}", error),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl<const N: usize> Checkpoint<N> {
+    pub fn read(path: &Path) -> Result<Checkpoint<N>, CheckpointError> {
+        let text: String = fs::read_to_string(path).map_err(CheckpointError::Io)?;
+        serde_json::from_str(&text).map_err(CheckpointError::Invalid)
+    }
+
+    /// Writes the checkpoint next to the file first and then moves it over the file,
+    /// so a process killed while writing leaves the previous checkpoint intact.
+    pub fn write(&self, path: &Path) -> Result<(), CheckpointError> {
+        let text: String = serde_json::to_string(self).map_err(CheckpointError::Invalid)?;
+        let mut temporary_path = path.as_os_str().to_owned();
+        temporary_path.push(".tmp");
+        fs::write(&temporary_path, text).map_err(CheckpointError::Io)?;
+        fs::rename(&temporary_path, path).map_err(CheckpointError::Io)
+    }
+}
@@ -0,0 +1,195 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use algo::{parse_instances, BatchResults, BatchRunner, Board, Goal, Instance, SearchStats, Solve, SolveError};
+use clap::{Args, ValueEnum};
+use serde_json::json;
+
+use super::solve::SolverArgs;
+use super::{with_tile_count, GoalLayout};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Args)]
+pub struct BatchArgs {
+    /// The instance file, read from stdin when left out
+    pub file: Option<PathBuf>,
+
+    /// The layout of the goal
+    #[arg(short, long, value_enum, default_value_t = GoalLayout::BlankLast)]
+    pub goal: GoalLayout,
+
+    #[command(flatten)]
+    pub solver: SolverArgs,
+
+    /// The number of worker threads, one for every thread the machine can run at once when left out
+    #[arg(short = 'j', long)]
+    pub workers: Option<usize>,
+
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Csv)]
+    pub report: ReportFormat,
+
+    /// Write the report to this file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// The outcome of solving a single instance.
+pub struct InstanceReport {
+    pub name: String,
+    pub board: Board,
+    pub status: &'static str,
+    pub length: Option<usize>,
+    pub stats: SearchStats,
+}
+
+pub fn run(args: &BatchArgs) -> Result<ExitCode, String> {
+    let instances: Vec<Instance> = read_instances(args.file.as_deref())?;
+
+    let runner: BatchRunner = match args.workers {
+        Some(0) => return Err("a batch needs at least one worker".to_string()),
+        Some(workers) => BatchRunner::new(workers),
+        None => BatchRunner::default(),
+    };
+    let indexed_instances: Vec<(usize, &Instance)> = instances.iter().enumerate().collect::<Vec<_>>();
+    let batch: BatchResults<Result<InstanceReport, String>> = runner.run(&indexed_instances, |(index, instance)| {
+        let report: Result<InstanceReport, String> = with_tile_count!(instance.board.len(), solve_instance(args, *index, instance));
+        if let Ok(report) = &report {
+            eprintln!("{} {}: {} {}", report.name, report.board, report.status, report.length.map_or(String::new(), |length| length.to_string()));
+        }
+        report
+    });
+    let reports: Vec<InstanceReport> = batch.results.into_iter().collect::<Result<Vec<_>, _>>()?;
+    let batch: BatchResults<InstanceReport> = BatchResults { results: reports, workers: batch.workers, elapsed: batch.elapsed };
+    let reports: &[InstanceReport] = &batch.results;
+
+    let report: String = match args.report {
+        ReportFormat::Csv => csv_report(reports),
+        ReportFormat::Json => json_report(args, &batch),
+    };
+    match &args.output {
+        Some(output) => fs::write(output, report).map_err(|error| format!("could not write {}: {}", output.display(), error))?,
+        None => print!("{}", report),
+    }
+
+    let solved: usize = reports.iter().filter(|report| report.length.is_some()).count();
+    eprintln!(
+        "Solved {}/{} instances in {:.3?} with {} workers, {:.1} instances per second",
+        solved,
+        reports.len(),
+        batch.elapsed,
+        batch.workers,
+        batch.throughput()
+    );
+    Ok(if solved == reports.len() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+/// Reads the instances from the file, or from stdin when there is none.
+pub fn read_instances(file: Option<&Path>) -> Result<Vec<Instance>, String> {
+    let text: String = match file {
+        Some(file) => fs::read_to_string(file).map_err(|error| format!("could not read {}: {}", file.display(), error))?,
+        None => {
+            let mut text: String = String::new();
+            io::stdin().read_to_string(&mut text).map_err(|error| format!("could not read the instances from stdin: {}", error))?;
+            text
+        }
+    };
+    parse_instances(&text).map_err(|error| error.to_string())
+}
+
+pub fn solve_instance<const N: usize>(args: &BatchArgs, index: usize, instance: &Instance) -> Result<InstanceReport, String> {
+    let mut puzzle_state: [i8; N] = instance.puzzle_state().expect("the tile count matches the board");
+    let goal: Goal<N> = args.goal.goal(instance.board)?;
+    let mut solver: Box<dyn Solve<N>> = args.solver.create(goal);
+
+    let (status, length, stats) = match solver.solve(&mut puzzle_state) {
+        Ok(solved_state) => ("solved", Some(solved_state.moves.len()), solved_state.stats),
+        Err(SolveError::Unsolvable) => ("unsolvable", None, SearchStats::default()),
+        Err(SolveError::LimitReached(stats)) => ("limit-reached", None, stats),
+        Err(SolveError::CheckpointMismatch) => ("checkpoint-mismatch", None, SearchStats::default()),
+    };
+    Ok(InstanceReport {
+        name: instance.name_or(index),
+        board: instance.board,
+        status,
+        length,
+        stats,
+    })
+}
+
+/// Adds up the work over every instance, the length only over the solved ones.
+pub fn totals(reports: &[InstanceReport]) -> (usize, usize, SearchStats) {
+    let solved: usize = reports.iter().filter(|report| report.length.is_some()).count();
+    let length: usize = reports.iter().filter_map(|report| report.length).sum();
+    let stats: SearchStats = reports.iter().fold(SearchStats::default(), |total, report| SearchStats {
+        expanded: total.expanded + report.stats.expanded,
+        generated: total.generated + report.stats.generated,
+        elapsed: total.elapsed + report.stats.elapsed,
+    });
+    (solved, length, stats)
+}
+
+pub fn csv_report(reports: &[InstanceReport]) -> String {
+    let mut csv: String = String::from("instance,board,status,length,expanded,generated,time_ms\n");
+    for report in reports {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{:.3}\n",
+            report.name.replace(',', " "),
+            report.board,
+            report.status,
+            report.length.map_or(String::new(), |length| length.to_string()),
+            report.stats.expanded,
+            report.stats.generated,
+            report.stats.elapsed.as_secs_f64() * 1000.0,
+        ));
+    }
+    let (solved, length, stats) = totals(reports);
+    csv.push_str(&format!(
+        "total,,{}/{} solved,{},{},{},{:.3}\n",
+        solved,
+        reports.len(),
+        length,
+        stats.expanded,
+        stats.generated,
+        stats.elapsed.as_secs_f64() * 1000.0,
+    ));
+    csv
+}
+
+pub fn json_report(args: &BatchArgs, batch: &BatchResults<InstanceReport>) -> String {
+    let reports: &[InstanceReport] = &batch.results;
+    let instances = reports
+        .iter()
+        .map(|report| {
+            json!({
+                "instance": report.name,
+                "board": report.board,
+                "status": report.status,
+                "length": report.length,
+                "stats": report.stats,
+            })
+        })
+        .collect::<Vec<_>>();
+    let (solved, length, stats) = totals(reports);
+    let output = json!({
+        "solver": args.solver.solver.name(),
+        "heuristic": args.solver.heuristic.name(),
+        "instances": instances,
+        "totals": {
+            "instances": reports.len(),
+            "solved": solved,
+            "length": length,
+            "stats": stats,
+        },
+        "workers": batch.workers,
+        "wall_ms": batch.elapsed.as_secs_f64() * 1000.0,
+        "instances_per_second": batch.throughput(),
+    });
+    format!("{:#}\n", output)
+}
@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use algo::{draw_puzzle_state, movable_piece, tile_label, BfsReport, Board, ExternalBfs, Goal, WILDCARD};
+use clap::Args;
+use serde_json::json;
+
+use super::{with_tile_count, Format, GoalLayout};
+
+#[derive(Debug, Args)]
+pub struct BfsArgs {
+    /// The board as WIDTHxHEIGHT
+    #[arg(short, long)]
+    pub board: Board,
+
+    /// The layout of the goal
+    #[arg(short, long, value_enum, default_value_t = GoalLayout::BlankLast)]
+    pub goal: GoalLayout,
+
+    /// Only tell these tiles apart, e.g. 1,2,3,4; every other tile is the same. All tiles when left out
+    #[arg(short, long, value_delimiter = ',')]
+    pub tiles: Vec<usize>,
+
+    /// The directory the layer files are written to
+    #[arg(short, long)]
+    pub directory: PathBuf,
+
+    /// The number of states sorted in memory at once, 4194304 when left out
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub buffer_states: Option<u64>,
+
+    /// The number of states furthest from the goal to show
+    #[arg(short, long, default_value_t = 5)]
+    pub examples: usize,
+
+    /// Keep the file of every layer instead of only the ones the search still needs
+    #[arg(long)]
+    pub keep_layers: bool,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+pub fn run(args: &BfsArgs) -> Result<ExitCode, String> {
+    with_tile_count!(args.board.len(), bfs(args))
+}
+
+fn bfs<const N: usize>(args: &BfsArgs) -> Result<ExitCode, String> {
+    let mut goal: Goal<N> = args.goal.goal(args.board)?;
+    if !args.tiles.is_empty() {
+        if let Some(label) = args.tiles.iter().find(|label| **label == 0 || **label >= N) {
+            return Err(format!("there is no tile {} on a {} board", label, args.board));
+        }
+        let mut tiles: Vec<i8> = args.tiles.iter().map(|label| *label as i8 - 1).collect::<Vec<_>>();
+        tiles.push(movable_piece(N));
+        goal = goal.only_tiles(&tiles);
+    }
+
+    let mut bfs: ExternalBfs<N> = ExternalBfs::new(goal, &args.directory).with_examples(args.examples).with_keep_layers(args.keep_layers);
+    if let Some(buffer_states) = args.buffer_states {
+        bfs = bfs.with_buffer_states(buffer_states as usize);
+    }
+    let report: BfsReport<N> = bfs
+        .run_with_progress(|depth, size| eprintln!("layer {}: {} states", depth, size))
+        .map_err(|error| format!("{}: {}", args.directory.display(), error))?;
+
+    match args.format {
+        Format::Json => {
+            let antipodes = report
+                .antipodes
+                .iter()
+                .map(|antipode| antipode.iter().map(|tile| (*tile != WILDCARD).then(|| tile_label::<N>(*tile))).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let output = json!({
+                "board": args.board,
+                "tiles": (!args.tiles.is_empty()).then_some(&args.tiles),
+                "layer_sizes": report.layer_sizes,
+                "total": report.total(),
+                "max_depth": report.max_depth(),
+                "antipodes": antipodes,
+            });
+            println!("{}", output);
+        }
+        Format::Text => {
+            println!("depth states");
+            for (depth, size) in report.layer_sizes.iter().enumerate() {
+                println!("{:>5} {}", depth, size);
+            }
+            println!("{} states, the furthest are {} moves from the goal", report.total(), report.max_depth());
+            for antipode in &report.antipodes {
+                println!();
+                println!("{}", draw_puzzle_state(antipode, &args.board));
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
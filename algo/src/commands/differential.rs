@@ -0,0 +1,148 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use algo::{format_instances, format_puzzle_state, tile_label, BatchRunner, Board, DifferentialReport, DifferentialTester, Goal, Heuristic, InstanceCheck, ScrambleMode, TieBreaking};
+use clap::Args;
+use serde_json::{json, Value};
+
+use super::solve::{SolverArgs, SolverKind};
+use super::{parse_seconds, with_tile_count, Format, GoalLayout};
+
+#[derive(Debug, Args)]
+pub struct DifferentialArgs {
+    /// The board as WIDTHxHEIGHT[:TOPOLOGY[:BLOCKED]]
+    #[arg(short, long, default_value_t = Board::default())]
+    pub board: Board,
+
+    /// The layout of the goal
+    #[arg(short, long, value_enum, default_value_t = GoalLayout::BlankLast)]
+    pub goal: GoalLayout,
+
+    /// The seed of the puzzle states, a random one when left out
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// The number of puzzle states to check
+    #[arg(short = 'n', long, default_value_t = DifferentialTester::<9>::DEFAULT_INSTANCES)]
+    pub instances: usize,
+
+    /// Scramble by moving the blank this many times instead of shuffling the tiles, for boards too large to solve from a shuffle
+    #[arg(long)]
+    pub walk_length: Option<usize>,
+
+    /// The solvers to compare, separated by commas. They all find the shortest moves, so they have to agree
+    #[arg(short, long, value_enum, value_delimiter = ',', default_values_t = [SolverKind::AStar, SolverKind::IdaStar, SolverKind::HdaStar, SolverKind::Bidirectional, SolverKind::SmaStar])]
+    pub solvers: Vec<SolverKind>,
+
+    /// The estimate of the remaining moves: manhattan, misplaced-tiles or linear-conflict
+    #[arg(short = 'H', long, default_value_t = Heuristic::Manhattan)]
+    pub heuristic: Heuristic,
+
+    /// Let every solver give up after expanding this many nodes, which isn't counted as a failure
+    #[arg(long)]
+    pub max_expanded: Option<u64>,
+
+    /// Let every solver give up after this many seconds, which isn't counted as a failure
+    #[arg(long, value_parser = parse_seconds)]
+    pub time_limit: Option<Duration>,
+
+    /// The number of worker threads, one for every thread the machine can run at once when left out
+    #[arg(short = 'j', long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub workers: Option<u16>,
+
+    /// Write the smallest puzzle state of every failure to this instance file
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+pub fn run(args: &DifferentialArgs) -> Result<ExitCode, String> {
+    with_tile_count!(args.board.len(), differential(args))
+}
+
+fn differential<const N: usize>(args: &DifferentialArgs) -> Result<ExitCode, String> {
+    if args.solvers.is_empty() {
+        return Err("there are no solvers to compare".to_string());
+    }
+    let goal: Goal<N> = args.goal.goal(args.board)?;
+    let seed: u64 = args.seed.unwrap_or_else(rand::random);
+    let scramble_mode: ScrambleMode = args.walk_length.map_or(ScrambleMode::Shuffle, ScrambleMode::RandomWalk);
+
+    let mut tester: DifferentialTester<N> = DifferentialTester::with_goal(seed, goal.clone()).with_instances(args.instances).with_scramble_mode(scramble_mode);
+    if let Some(workers) = args.workers {
+        tester = tester.with_runner(BatchRunner::new(workers as usize));
+    }
+    for solver in &args.solvers {
+        let solver_args: SolverArgs = SolverArgs {
+            solver: *solver,
+            heuristic: args.heuristic,
+            tie_breaking: TieBreaking::Lifo,
+            max_expanded: args.max_expanded,
+            time_limit: args.time_limit,
+            threads: None,
+            max_nodes: None,
+        };
+        let solver_goal: Goal<N> = goal.clone();
+        tester = tester.with_solver(solver.name(), move || solver_args.create(solver_goal.clone()));
+    }
+
+    let report: DifferentialReport<N> = tester.run().map_err(|error| error.to_string())?;
+    let reproducing: String = format_instances(&report.reproducing_instances(&goal));
+    if let Some(output) = &args.output {
+        fs::write(output, &reproducing).map_err(|error| format!("could not write {}: {}", output.display(), error))?;
+    }
+
+    let solvers: Vec<&str> = args.solvers.iter().map(SolverKind::name).collect::<Vec<_>>();
+    let gave_up: usize = report.checks.iter().map(|check| check.gave_up.len()).sum();
+    match args.format {
+        Format::Json => {
+            let output = json!({
+                "seed": seed,
+                "instances": report.checks.len(),
+                "solvers": solvers,
+                "gave_up": gave_up,
+                "failures": report.failures.iter().map(|failure| json!({
+                    "instance": failure.index + 1,
+                    "check": check_json(&report.checks[failure.index]),
+                    "minimal": check_json(&failure.minimal),
+                })).collect::<Vec<_>>(),
+            });
+            println!("{}", output);
+        }
+        Format::Text => {
+            for (failure, instance) in report.failures.iter().zip(reproducing.lines()) {
+                let check: &InstanceCheck<N> = &report.checks[failure.index];
+                println!("Instance {}: {}", failure.index + 1, format_puzzle_state(&check.puzzle_state));
+                for discrepancy in &check.discrepancies {
+                    println!("  {}", discrepancy);
+                }
+                println!("Smallest failing instance: {}", instance);
+                for discrepancy in &failure.minimal.discrepancies {
+                    println!("  {}", discrepancy);
+                }
+            }
+            println!(
+                "Checked {} instances from seed {} with {}: {} failed, {} answers gave up",
+                report.checks.len(),
+                seed,
+                solvers.join(", "),
+                report.failures.len(),
+                gave_up
+            );
+        }
+    }
+    Ok(if report.is_ok() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn check_json<const N: usize>(check: &InstanceCheck<N>) -> Value {
+    json!({
+        "puzzle_state": check.puzzle_state.iter().map(|tile| tile_label::<N>(*tile)).collect::<Vec<_>>(),
+        "lengths": check.lengths.iter().map(|(solver, length)| json!({ "solver": solver, "length": length })).collect::<Vec<_>>(),
+        "gave_up": check.gave_up,
+        "discrepancies": check.discrepancies.iter().map(ToString::to_string).collect::<Vec<_>>(),
+    })
+}
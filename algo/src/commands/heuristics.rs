@@ -0,0 +1,183 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use algo::{BatchRunner, Board, Goal, Heuristic, HeuristicAnalysis, HeuristicQuality, Instance, InstanceQuality, SearchLimits};
+use clap::Args;
+use serde_json::{json, Value};
+
+use super::batch::read_instances;
+use super::{parse_seconds, with_tile_count, Format, GoalLayout};
+
+#[derive(Debug, Args)]
+pub struct HeuristicsArgs {
+    /// The instance file, read from stdin when left out
+    pub file: Option<PathBuf>,
+
+    /// The layout of the goal
+    #[arg(short, long, value_enum, default_value_t = GoalLayout::BlankLast)]
+    pub goal: GoalLayout,
+
+    /// The heuristics to compare, separated by commas
+    #[arg(short = 'H', long, value_delimiter = ',', default_values_t = Heuristic::ALL)]
+    pub heuristics: Vec<Heuristic>,
+
+    /// Give up on an instance after expanding this many nodes
+    #[arg(long)]
+    pub max_expanded: Option<u64>,
+
+    /// Give up on an instance after this many seconds
+    #[arg(long, value_parser = parse_seconds)]
+    pub time_limit: Option<Duration>,
+
+    /// The number of worker threads, one for every thread the machine can run at once when left out
+    #[arg(short = 'j', long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub workers: Option<u16>,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+/// The quality of every heuristic on the instances of a single board.
+struct BoardReport {
+    board: Board,
+    names: Vec<String>,
+    qualities: Vec<HeuristicQuality>,
+}
+
+pub fn run(args: &HeuristicsArgs) -> Result<ExitCode, String> {
+    if args.heuristics.is_empty() {
+        return Err("there are no heuristics to compare".to_string());
+    }
+    let instances: Vec<Instance> = read_instances(args.file.as_deref())?;
+
+    // every board is reported on its own, in the order it first shows up
+    let mut boards: Vec<(Board, Vec<(usize, &Instance)>)> = Vec::new();
+    for (index, instance) in instances.iter().enumerate() {
+        match boards.iter_mut().find(|(board, _)| *board == instance.board) {
+            Some((_, board_instances)) => board_instances.push((index, instance)),
+            None => boards.push((instance.board, vec![(index, instance)])),
+        }
+    }
+    let reports: Vec<BoardReport> = boards
+        .iter()
+        .map(|(board, board_instances)| with_tile_count!(board.len(), analyse(args, *board, board_instances)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match args.format {
+        Format::Json => println!("{:#}", json!(reports.iter().map(json_report).collect::<Vec<_>>())),
+        Format::Text => {
+            let text: Vec<String> = reports.iter().map(text_report).collect::<Vec<_>>();
+            print!("{}", text.join("\n"));
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn analyse<const N: usize>(args: &HeuristicsArgs, board: Board, instances: &[(usize, &Instance)]) -> Result<BoardReport, String> {
+    let goal: Goal<N> = args.goal.goal(board)?;
+    let limits: SearchLimits = SearchLimits {
+        max_expanded: args.max_expanded,
+        time_limit: args.time_limit,
+        ..SearchLimits::none()
+    };
+    let mut analysis: HeuristicAnalysis<N> = HeuristicAnalysis::with_goal(goal).with_heuristics(&args.heuristics).with_limits(limits);
+    if let Some(workers) = args.workers {
+        analysis = analysis.with_runner(BatchRunner::new(workers as usize));
+    }
+
+    let puzzle_states: Vec<[i8; N]> = instances.iter().map(|(_, instance)| instance.puzzle_state().expect("the tile count matches the board")).collect::<Vec<_>>();
+    Ok(BoardReport {
+        board,
+        names: instances.iter().map(|(index, instance)| instance.name_or(*index)).collect::<Vec<_>>(),
+        qualities: analysis.analyse(&puzzle_states),
+    })
+}
+
+/// Writes a number with the given decimals, or a dash when it is unknown.
+fn optional(value: Option<f64>, decimals: usize) -> String {
+    value.map_or("-".to_string(), |value| format!("{:.*}", decimals, value))
+}
+
+fn text_report(report: &BoardReport) -> String {
+    let mut text: String = format!(
+        "{}, {} instances, mean optimal distance {}\n",
+        report.board,
+        report.names.len(),
+        optional(report.qualities[0].mean_distance(), 2)
+    );
+    text.push_str(&format!("{:<16} {:>8} {:>10} {:>9} {:>14} {:>8}\n", "heuristic", "mean h", "mean h/d", "mean EBF", "mean expanded", "gave up"));
+    for quality in &report.qualities {
+        text.push_str(&format!(
+            "{:<16} {:>8} {:>10} {:>9} {:>14} {:>8}\n",
+            quality.heuristic.name(),
+            optional(quality.mean_estimate(), 2),
+            optional(quality.mean_ratio(), 3),
+            optional(quality.mean_effective_branching_factor(), 3),
+            optional(quality.mean_expanded(), 1),
+            quality.gave_up()
+        ));
+    }
+
+    text.push_str("\nMoves h falls short of the optimal distance: instances\n");
+    for quality in &report.qualities {
+        let distribution: Vec<String> = quality.shortfall_distribution().iter().map(|(shortfall, count)| format!("{}: {}", shortfall, count)).collect::<Vec<_>>();
+        text.push_str(&format!("{:<16} {}\n", quality.heuristic.name(), distribution.join(", ")));
+    }
+
+    text.push_str("\nNodes expanded per instance\n");
+    text.push_str(&format!("{:<16} {:>9}", "instance", "distance"));
+    for quality in &report.qualities {
+        text.push_str(&format!(" {:>16}", quality.heuristic.name()));
+    }
+    text.push('\n');
+    for (index, name) in report.names.iter().enumerate() {
+        let distance: Option<f64> = report.qualities[0].instances[index].distance.map(|distance| distance as f64);
+        text.push_str(&format!("{:<16} {:>9}", name, optional(distance, 0)));
+        for quality in &report.qualities {
+            let expanded: Option<f64> = quality.instances[index].stats.map(|stats| stats.expanded as f64);
+            text.push_str(&format!(" {:>16}", optional(expanded, 0)));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn json_report(report: &BoardReport) -> Value {
+    let heuristics = report
+        .qualities
+        .iter()
+        .map(|quality| {
+            let instances = report
+                .names
+                .iter()
+                .zip(&quality.instances)
+                .map(|(name, instance): (&String, &InstanceQuality)| {
+                    json!({
+                        "instance": name,
+                        "estimate": instance.estimate,
+                        "distance": instance.distance,
+                        "stats": instance.stats,
+                        "effective_branching_factor": instance.effective_branching_factor(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            json!({
+                "heuristic": quality.heuristic,
+                "mean_estimate": quality.mean_estimate(),
+                "mean_ratio": quality.mean_ratio(),
+                "shortfall_distribution": quality.shortfall_distribution().iter().map(|(shortfall, count)| (shortfall.to_string(), json!(count))).collect::<serde_json::Map<_, _>>(),
+                "mean_effective_branching_factor": quality.mean_effective_branching_factor(),
+                "mean_expanded": quality.mean_expanded(),
+                "gave_up": quality.gave_up(),
+                "instances": instances,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "board": report.board,
+        "instances": report.names.len(),
+        "mean_distance": report.qualities[0].mean_distance(),
+        "heuristics": heuristics,
+    })
+}
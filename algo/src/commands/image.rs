@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use algo::{format_puzzle_state, parse_puzzle_state, tile_label, Board, Goal, Image, ImagePuzzle, ScrambleMode, Scrambler};
+use clap::Args;
+use serde_json::json;
+
+use super::{with_tile_count, Format, GoalLayout};
+
+#[derive(Debug, Args)]
+pub struct ImageArgs {
+    /// The picture to cut into tiles, a PNG or binary PPM image
+    pub input: PathBuf,
+
+    /// The tiles in reading order to draw, numbered from 1 with 0 or _ for the movable piece. Scrambled from --seed when left out
+    pub tiles: Vec<String>,
+
+    /// The board as WIDTHxHEIGHT[:TOPOLOGY[:BLOCKED]], the picture is cut into its positions
+    #[arg(short, long, default_value_t = Board::default())]
+    pub board: Board,
+
+    /// The layout of the goal, which tells the part of the picture every tile shows
+    #[arg(short, long, value_enum, default_value_t = GoalLayout::BlankLast)]
+    pub goal: GoalLayout,
+
+    /// The seed of the scrambled puzzle, a random one when left out
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// The image to write, as PNG when it ends in .png and as binary PPM otherwise
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+pub fn run(args: &ImageArgs) -> Result<ExitCode, String> {
+    with_tile_count!(args.board.len(), image(args))
+}
+
+fn image<const N: usize>(args: &ImageArgs) -> Result<ExitCode, String> {
+    let goal: Goal<N> = args.goal.goal(args.board)?;
+    let picture: Image = Image::read(&args.input).map_err(|error| format!("{}: {}", args.input.display(), error))?;
+    let image_puzzle: ImagePuzzle<N> = ImagePuzzle::new(picture, goal.clone()).map_err(|error| format!("{}: {}", args.input.display(), error))?;
+    let puzzle_state: [i8; N] = if args.tiles.is_empty() {
+        let seed: u64 = args.seed.unwrap_or_else(rand::random);
+        Scrambler::with_goal(seed, goal).scramble(ScrambleMode::Shuffle).map_err(|error| error.to_string())?
+    } else {
+        parse_puzzle_state(&args.tiles.join(" ")).map_err(|error| error.to_string())?
+    };
+
+    image_puzzle.render(&puzzle_state).write(&args.output).map_err(|error| format!("{}: {}", args.output.display(), error))?;
+
+    match args.format {
+        Format::Json => {
+            let (tile_width, tile_height) = image_puzzle.tile_size();
+            let output = json!({
+                "board": args.board,
+                "puzzle_state": puzzle_state.iter().map(|tile| tile_label::<N>(*tile)).collect::<Vec<_>>(),
+                "tile_size": [tile_width, tile_height],
+                "output": args.output,
+            });
+            println!("{}", output);
+        }
+        Format::Text => {
+            println!("{}", format_puzzle_state(&puzzle_state));
+            println!("Wrote {}", args.output.display());
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,107 @@
+use std::process::ExitCode;
+use std::time::Duration;
+
+use algo::{parse_labels, Board, LabelledAStar, LabelledGoal, LabelledSolution, SearchLimits, SolveError, BLANK_LABEL};
+use clap::Args;
+use serde_json::json;
+
+use super::solve::stats_text;
+use super::{parse_seconds, read_tiles, with_tile_count, Format};
+
+#[derive(Debug, Args)]
+pub struct LabelledArgs {
+    /// The labels in reading order, labels can repeat and 0 or _ marks a blank. Read from stdin when left out
+    pub tiles: Vec<String>,
+
+    /// The board as WIDTHxHEIGHT[:TOPOLOGY[:BLOCKED]]; square flat boards are recognised from the number of labels
+    #[arg(short, long)]
+    pub board: Option<Board>,
+
+    /// The labels of the goal, e.g. "1 1 2 2 _ _"; the labels in increasing order followed by the blanks when left out
+    #[arg(short, long)]
+    pub goal: Option<String>,
+
+    /// Give up after expanding this many nodes
+    #[arg(long)]
+    pub max_expanded: Option<u64>,
+
+    /// Give up after this many seconds
+    #[arg(long, value_parser = parse_seconds)]
+    pub time_limit: Option<Duration>,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+pub fn run(args: &LabelledArgs) -> Result<ExitCode, String> {
+    let (text, board) = read_tiles(&args.tiles, args.board)?;
+    with_tile_count!(board.len(), solve(args, board, &text))
+}
+
+fn solve<const N: usize>(args: &LabelledArgs, board: Board, text: &str) -> Result<ExitCode, String> {
+    let start: [i8; N] = parse_labels(text).map_err(|error| error.to_string())?;
+    let goal_labels: [i8; N] = match &args.goal {
+        Some(goal) => parse_labels(goal).map_err(|error| format!("goal: {}", error))?,
+        None => {
+            let mut goal_labels: [i8; N] = start;
+            goal_labels.sort_by_key(|label| (*label == BLANK_LABEL, *label));
+            goal_labels
+        }
+    };
+    let goal: LabelledGoal<N> = LabelledGoal::new(board, goal_labels).map_err(|error| error.to_string())?;
+    let limits: SearchLimits = SearchLimits {
+        max_expanded: args.max_expanded,
+        time_limit: args.time_limit,
+        ..SearchLimits::none()
+    };
+    let result: Result<LabelledSolution<N>, SolveError> = LabelledAStar::with_goal(goal).with_limits(limits).solve(&start);
+    let moves = |solution: &LabelledSolution<N>| solution.moves.iter().map(|labelled_move| labelled_move.to_string()).collect::<Vec<_>>().join(" ");
+
+    match args.format {
+        Format::Json => {
+            let mut output = json!({
+                "board": board,
+                "start": start.to_vec(),
+                "goal": goal_labels.to_vec(),
+            });
+            match &result {
+                Ok(solution) => {
+                    output["status"] = json!("solved");
+                    output["moves"] = json!(moves(solution));
+                    output["length"] = json!(solution.moves.len());
+                    output["stats"] = json!(solution.stats);
+                }
+                Err(SolveError::Unsolvable) => output["status"] = json!("unsolvable"),
+                Err(SolveError::CheckpointMismatch) => output["status"] = json!("checkpoint-mismatch"),
+                Err(SolveError::LimitReached(stats)) => {
+                    output["status"] = json!("limit-reached");
+                    output["stats"] = json!(stats);
+                }
+            }
+            println!("{}", output);
+        }
+        Format::Text => {
+            for (row, goal_row) in start.chunks(board.width()).zip(goal_labels.chunks(board.width())) {
+                println!("{}    {}", format_row(row), format_row(goal_row));
+            }
+            println!();
+            match &result {
+                Ok(solution) => {
+                    println!("Solved in {} moves: {}", solution.moves.len(), moves(solution));
+                    println!("{}", stats_text(&solution.stats));
+                }
+                Err(error) => println!("Not solved, {}", error),
+            }
+        }
+    }
+
+    Ok(if result.is_ok() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+/// Writes a row of labels right aligned, with `_` for the blanks.
+fn format_row(row: &[i8]) -> String {
+    row.iter()
+        .map(|label| format!("{:>3}", if *label == BLANK_LABEL { "_".to_string() } else { label.to_string() }))
+        .collect::<Vec<_>>()
+        .join("")
+}
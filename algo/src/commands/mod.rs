@@ -0,0 +1,138 @@
+pub mod batch;
+pub mod bfs;
+pub mod differential;
+pub mod heuristics;
+pub mod image;
+pub mod labelled;
+pub mod optimise;
+pub mod play;
+pub mod scramble;
+pub mod solve;
+pub mod verify;
+
+use std::io::{self, Read};
+use std::time::Duration;
+
+use algo::{movable_piece, Board, Goal, MoveNotation};
+use clap::{Args, ValueEnum};
+
+/// Calls the function with the tile count as const generic, for every tile count the binary supports.
+macro_rules! with_tile_count {
+    ($len:expr, $function:ident($($argument:expr),*)) => {
+        match $len {
+            4 => $function::<4>($($argument),*),
+            6 => $function::<6>($($argument),*),
+            8 => $function::<8>($($argument),*),
+            9 => $function::<9>($($argument),*),
+            10 => $function::<10>($($argument),*),
+            12 => $function::<12>($($argument),*),
+            15 => $function::<15>($($argument),*),
+            16 => $function::<16>($($argument),*),
+            20 => $function::<20>($($argument),*),
+            24 => $function::<24>($($argument),*),
+            25 => $function::<25>($($argument),*),
+            len => Err(format!("boards with {} positions are not supported", len)),
+        }
+    };
+}
+
+pub(crate) use with_tile_count;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum GoalLayout {
+    BlankLast,
+    BlankFirst,
+    Spiral,
+    Snake,
+}
+
+impl GoalLayout {
+    /// Lays out the goal on the board, which fails when the movable piece would end on a blocked position.
+    pub fn goal<const N: usize>(&self, board: Board) -> Result<Goal<N>, String> {
+        let layout = |board: Board| match self {
+            GoalLayout::BlankLast => Goal::blank_last(board),
+            GoalLayout::BlankFirst => Goal::blank_first(board),
+            GoalLayout::Spiral => Goal::spiral(board),
+            GoalLayout::Snake => Goal::snake(board),
+        };
+        let open_goal: Goal<N> = layout(Board::new(board.width(), board.height()));
+        let movable_piece_position: usize = open_goal.position_of(movable_piece(N)).unwrap();
+        if board.is_blocked(movable_piece_position) {
+            return Err(format!("the goal puts the movable piece on blocked position {}", movable_piece_position));
+        }
+        Ok(layout(board))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Notation {
+    /// Letters tell where the movable piece goes
+    Blank,
+    /// Letters tell where the tile next to the movable piece slides
+    Tile,
+}
+
+impl From<Notation> for MoveNotation {
+    fn from(notation: Notation) -> MoveNotation {
+        match notation {
+            Notation::Blank => MoveNotation::Blank,
+            Notation::Tile => MoveNotation::Tile,
+        }
+    }
+}
+
+/// The puzzle state a command works on.
+#[derive(Debug, Args)]
+pub struct PuzzleArgs {
+    /// The tiles in reading order, numbered from 1 with 0 or _ for the movable piece. Read from stdin when left out
+    pub tiles: Vec<String>,
+
+    /// The board as WIDTHxHEIGHT[:TOPOLOGY[:BLOCKED]], e.g. 4x4:torus or 3x3:flat:4; square flat boards are recognised from the number of tiles
+    #[arg(short, long)]
+    pub board: Option<Board>,
+
+    /// The layout of the goal
+    #[arg(short, long, value_enum, default_value_t = GoalLayout::BlankLast)]
+    pub goal: GoalLayout,
+}
+
+impl PuzzleArgs {
+    /// Gets the written tiles and the board they are on.
+    pub fn read(&self) -> Result<(String, Board), String> {
+        read_tiles(&self.tiles, self.board)
+    }
+}
+
+/// Joins the tiles given on the command line, or reads them from stdin when there are none,
+/// and gets the board they are on.
+pub fn read_tiles(tiles: &[String], board: Option<Board>) -> Result<(String, Board), String> {
+    let text: String = if tiles.is_empty() {
+        let mut text: String = String::new();
+        io::stdin().read_to_string(&mut text).map_err(|error| format!("could not read the tiles from stdin: {}", error))?;
+        text
+    } else {
+        tiles.join(" ")
+    };
+    let len: usize = text.split(|c: char| c.is_whitespace() || c == ',').filter(|label| !label.is_empty()).count();
+    let board: Board = match board {
+        Some(board) => board,
+        None => square_board(len).ok_or_else(|| format!("{} tiles don't form a square board, pass --board WIDTHxHEIGHT", len))?,
+    };
+    Ok((text, board))
+}
+
+pub fn square_board(len: usize) -> Option<Board> {
+    (2..=len).find(|size| size * size == len).map(Board::square)
+}
+
+/// Parses a number of seconds for a flag, which can't be negative, NaN or too large for a `Duration`.
+pub fn parse_seconds(text: &str) -> Result<Duration, String> {
+    let seconds: f64 = text.parse::<f64>().map_err(|error| error.to_string())?;
+    Duration::try_from_secs_f64(seconds).map_err(|_| "expected a number of seconds from 0 up to 2^64".to_string())
+}
@@ -0,0 +1,84 @@
+use std::process::ExitCode;
+
+use algo::{parse_puzzle_state, Board, Direction, MoveNotation, OptimisedMoves, SolutionOptimiser, WindowSearch};
+use clap::{Args, ValueEnum};
+use serde_json::json;
+
+use super::{with_tile_count, Format, Notation, PuzzleArgs};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Search {
+    /// A* between the ends of every window, giving up after --max-expanded nodes
+    #[value(name = "astar")]
+    AStar,
+    /// Distance tables for every position of the blank, for puzzles of up to 10 tiles. Larger ones fall back to astar
+    Table,
+}
+
+#[derive(Debug, Args)]
+pub struct OptimiseArgs {
+    #[command(flatten)]
+    pub puzzle: PuzzleArgs,
+
+    /// The moves to shorten, e.g. LURD
+    #[arg(short, long)]
+    pub moves: String,
+
+    /// The number of moves replaced at once
+    #[arg(short, long, default_value_t = SolutionOptimiser::<9>::DEFAULT_WINDOW as u16, value_parser = clap::value_parser!(u16).range(2..))]
+    pub window: u16,
+
+    /// How the shortest moves of a window are found
+    #[arg(short, long, value_enum, default_value_t = Search::AStar)]
+    pub search: Search,
+
+    /// Give up on a window after A* expanded this many nodes
+    #[arg(long, default_value_t = SolutionOptimiser::<9>::DEFAULT_MAX_EXPANDED)]
+    pub max_expanded: u64,
+
+    /// How the moves are written
+    #[arg(short, long, value_enum, default_value_t = Notation::Blank)]
+    pub notation: Notation,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+pub fn run(args: &OptimiseArgs) -> Result<ExitCode, String> {
+    let (text, board) = args.puzzle.read()?;
+    with_tile_count!(board.len(), optimise(args, board, &text))
+}
+
+fn optimise<const N: usize>(args: &OptimiseArgs, board: Board, text: &str) -> Result<ExitCode, String> {
+    let puzzle_state: [i8; N] = parse_puzzle_state(text).map_err(|error| error.to_string())?;
+    let notation: MoveNotation = args.notation.into();
+    let moves: Vec<Direction> = notation.parse(&args.moves).map_err(|error| error.to_string())?;
+    let window_search: WindowSearch = match args.search {
+        Search::AStar => WindowSearch::AStar { max_expanded: args.max_expanded },
+        Search::Table => WindowSearch::DistanceTable { max_expanded: args.max_expanded },
+    };
+
+    let optimised: OptimisedMoves = SolutionOptimiser::new(board)
+        .with_window(args.window as usize)
+        .with_window_search(window_search)
+        .optimise(&puzzle_state, &moves)
+        .map_err(|illegal_move| format!("move {} ({}) takes the movable piece off the board", illegal_move.index + 1, illegal_move.direction))?;
+
+    match args.format {
+        Format::Json => {
+            let output = json!({
+                "moves": notation.format(&optimised.moves),
+                "length": optimised.moves.len(),
+                "original_length": optimised.original_length,
+                "saved": optimised.saved(),
+                "replaced_windows": optimised.replaced_windows,
+            });
+            println!("{}", output);
+        }
+        Format::Text => {
+            println!("{} moves: {}", optimised.moves.len(), notation.format(&optimised.moves));
+            println!("Saved {} of {} moves, replaced windows: {}", optimised.saved(), optimised.original_length, optimised.replaced_windows);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,197 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use algo::{draw_puzzle_state, parse_puzzle_state, Board, Direction, Game, Goal, Heuristic, IdaStar, SavedGame, ScrambleMode, Scrambler, SearchLimits, SolveError};
+use clap::Args;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, style, terminal};
+
+use super::{square_board, with_tile_count, GoalLayout};
+
+const HELP: &str = "arrows/WASD slide a tile, h hint, u undo, r redo, k save, l load, q quit";
+
+#[derive(Debug, Args)]
+pub struct PlayArgs {
+    /// The tiles in reading order, numbered from 1 with 0 or _ for the movable piece. Scrambled from --seed when left out
+    pub tiles: Vec<String>,
+
+    /// The board as WIDTHxHEIGHT[:TOPOLOGY[:BLOCKED]]; square flat boards are recognised from the number of tiles
+    #[arg(short, long)]
+    pub board: Option<Board>,
+
+    /// The layout of the goal
+    #[arg(short, long, value_enum, default_value_t = GoalLayout::BlankLast)]
+    pub goal: GoalLayout,
+
+    /// The seed of the scrambled puzzle, a random one when left out
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// The file games are saved to and loaded from
+    #[arg(long, default_value = "algo-game.json")]
+    pub save_file: PathBuf,
+
+    /// Continue the game saved in --save-file instead of starting a new one
+    #[arg(long)]
+    pub load: bool,
+
+    /// The estimate the solver uses for hints: manhattan, misplaced-tiles or linear-conflict
+    #[arg(short = 'H', long, default_value_t = Heuristic::LinearConflict)]
+    pub heuristic: Heuristic,
+
+    /// Give up looking for a hint after this many seconds
+    #[arg(long, default_value_t = 10.0)]
+    pub hint_time_limit: f64,
+}
+
+pub fn run(args: &PlayArgs) -> Result<ExitCode, String> {
+    let len: usize = args.tiles.iter().flat_map(|tiles| tiles.split(|c: char| c.is_whitespace() || c == ',')).filter(|label| !label.is_empty()).count();
+    let board: Board = match args.board {
+        Some(board) => board,
+        None if args.load => saved_board(&args.save_file)?,
+        None if args.tiles.is_empty() => Board::default(),
+        None => square_board(len).ok_or_else(|| format!("{} tiles don't form a square board, pass --board WIDTHxHEIGHT", len))?,
+    };
+    with_tile_count!(board.len(), play(args, board))
+}
+
+fn play<const N: usize>(args: &PlayArgs, board: Board) -> Result<ExitCode, String> {
+    let hint_time_limit: Duration = Duration::try_from_secs_f64(args.hint_time_limit).map_err(|_| format!("invalid --hint-time-limit {}, expected a number of seconds from 0 up to 2^64", args.hint_time_limit))?;
+    let mut game: Game<N> = if args.load {
+        load(&args.save_file)?
+    } else {
+        let goal: Goal<N> = args.goal.goal(board)?;
+        let start: [i8; N] = if args.tiles.is_empty() {
+            let seed: u64 = args.seed.unwrap_or_else(rand::random);
+            Scrambler::with_goal(seed, goal.clone()).scramble(ScrambleMode::Shuffle).map_err(|error| error.to_string())?
+        } else {
+            parse_puzzle_state(&args.tiles.join(" ")).map_err(|error| error.to_string())?
+        };
+        if !goal.is_reachable_from(&start) {
+            return Err("the goal cannot be reached from this puzzle state".to_string());
+        }
+        Game::new(goal, start)
+    };
+
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode().map_err(|error| format!("could not set up the terminal: {}", error))?;
+    let _restore = RestoreTerminal;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide).map_err(|error| error.to_string())?;
+
+    let mut message: String = String::new();
+    loop {
+        draw(&mut stdout, &game, &message).map_err(|error| error.to_string())?;
+        let Event::Key(KeyEvent { code, modifiers, kind: KeyEventKind::Press, .. }) = event::read().map_err(|error| error.to_string())? else {
+            continue;
+        };
+        message.clear();
+        let slide: Option<Direction> = match code {
+            KeyCode::Up | KeyCode::Char('w') => Some(Direction::Up),
+            KeyCode::Down | KeyCode::Char('s') => Some(Direction::Down),
+            KeyCode::Left | KeyCode::Char('a') => Some(Direction::Left),
+            KeyCode::Right | KeyCode::Char('d') => Some(Direction::Right),
+            _ => None,
+        };
+        if let Some(direction) = slide {
+            if !game.slide(direction) {
+                message = "No tile can slide that way".to_string();
+            }
+            continue;
+        }
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Char('u') => {
+                if game.undo().is_none() {
+                    message = "Nothing to undo".to_string();
+                }
+            }
+            KeyCode::Char('r') => {
+                if game.redo().is_none() {
+                    message = "Nothing to redo".to_string();
+                }
+            }
+            KeyCode::Char('h') => {
+                draw(&mut stdout, &game, "Thinking...").map_err(|error| error.to_string())?;
+                message = hint(args, &game, hint_time_limit);
+            }
+            KeyCode::Char('k') => {
+                message = match game.saved().write(&args.save_file) {
+                    Ok(()) => format!("Saved to {}", args.save_file.display()),
+                    Err(error) => format!("Could not save: {}", error),
+                };
+            }
+            KeyCode::Char('l') => {
+                message = match load(&args.save_file) {
+                    Ok(loaded_game) => {
+                        game = loaded_game;
+                        format!("Loaded {}", args.save_file.display())
+                    }
+                    Err(error) => format!("Could not load: {}", error),
+                };
+            }
+            _ => message = HELP.to_string(),
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Reads the board of a saved game, which tells the number of tiles to read the rest with.
+fn saved_board(path: &Path) -> Result<Board, String> {
+    let text: String = fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    let saved_game: serde_json::Value = serde_json::from_str(&text).map_err(|error| format!("{}: not a valid saved game, {}", path.display(), error))?;
+    serde_json::from_value(saved_game["board"].clone()).map_err(|error| format!("{}: not a valid saved game, {}", path.display(), error))
+}
+
+fn load<const N: usize>(path: &Path) -> Result<Game<N>, String> {
+    let saved_game: SavedGame<N> = SavedGame::read(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    Game::from_saved(&saved_game).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+/// Asks the solver for the next move and tells which key slides the tile that way.
+fn hint<const N: usize>(args: &PlayArgs, game: &Game<N>, time_limit: Duration) -> String {
+    let limits: SearchLimits = SearchLimits { time_limit: Some(time_limit), ..SearchLimits::none() };
+    let mut solver: IdaStar<N> = IdaStar::with_goal(game.goal().clone()).with_heuristic(args.heuristic).with_limits(limits);
+    match game.hint(&mut solver) {
+        Ok(Some(direction)) => {
+            let key: &str = match direction.opposite() {
+                Direction::Up => "up (w)",
+                Direction::Down => "down (s)",
+                Direction::Left => "left (a)",
+                Direction::Right => "right (d)",
+                Direction::None => "",
+            };
+            format!("Hint: slide a tile {}", key)
+        }
+        Ok(None) => "Already solved".to_string(),
+        Err(SolveError::LimitReached(_)) => "No hint found in time".to_string(),
+        Err(error) => format!("No hint, {}", error),
+    }
+}
+
+fn draw<const N: usize>(stdout: &mut io::Stdout, game: &Game<N>, message: &str) -> io::Result<()> {
+    queue!(stdout, cursor::MoveTo(0, 0), terminal::Clear(terminal::ClearType::All))?;
+    let status: String = if game.is_solved() {
+        format!("Solved in {} moves!", game.moves().len())
+    } else {
+        format!("Moves: {}", game.moves().len())
+    };
+    let lines: Vec<String> = draw_puzzle_state(game.puzzle_state(), game.goal().board()).lines().map(str::to_string).collect::<Vec<_>>();
+    for line in lines.iter().chain([String::new(), status, message.to_string(), String::new(), HELP.to_string()].iter()) {
+        queue!(stdout, style::Print(line), style::Print("\r\n"))?;
+    }
+    stdout.flush()
+}
+
+/// Leaves the alternate screen and raw mode when the game ends, also when it ends with an error.
+struct RestoreTerminal;
+
+impl Drop for RestoreTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
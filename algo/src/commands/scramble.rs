@@ -0,0 +1,88 @@
+use std::process::ExitCode;
+
+use algo::{draw_puzzle_state, format_puzzle_state, tile_label, Board, ScrambleMode, Scrambler};
+use clap::{Args, ValueEnum};
+use serde_json::json;
+
+use super::{with_tile_count, Format, GoalLayout};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Mode {
+    /// Shuffle every tile, fixing the parity when needed
+    Shuffle,
+    /// Walk the movable piece --moves times from the goal
+    Walk,
+    /// Pick a puzzle that needs exactly --moves moves
+    Exact,
+}
+
+#[derive(Debug, Args)]
+pub struct ScrambleArgs {
+    /// The seed of the random generator, the same seed gives the same puzzles
+    #[arg(long)]
+    pub seed: u64,
+
+    /// The board as WIDTHxHEIGHT[:TOPOLOGY[:BLOCKED]], e.g. 4x4 or 3x3:torus
+    #[arg(short, long, default_value_t = Board::default())]
+    pub board: Board,
+
+    /// The layout of the goal
+    #[arg(short, long, value_enum, default_value_t = GoalLayout::BlankLast)]
+    pub goal: GoalLayout,
+
+    #[arg(short, long, value_enum, default_value_t = Mode::Shuffle)]
+    pub mode: Mode,
+
+    /// The difficulty for the walk and exact modes
+    #[arg(long, required_if_eq_any([("mode", "walk"), ("mode", "exact")]))]
+    pub moves: Option<usize>,
+
+    /// The number of puzzles to generate
+    #[arg(short = 'n', long, default_value_t = 1)]
+    pub count: usize,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+impl ScrambleArgs {
+    fn scramble_mode(&self) -> ScrambleMode {
+        match self.mode {
+            Mode::Shuffle => ScrambleMode::Shuffle,
+            Mode::Walk => ScrambleMode::RandomWalk(self.moves.unwrap_or(0)),
+            Mode::Exact => ScrambleMode::ExactDistance(self.moves.unwrap_or(0)),
+        }
+    }
+}
+
+pub fn run(args: &ScrambleArgs) -> Result<ExitCode, String> {
+    with_tile_count!(args.board.len(), scramble(args))
+}
+
+fn scramble<const N: usize>(args: &ScrambleArgs) -> Result<ExitCode, String> {
+    let mut scrambler: Scrambler<N> = Scrambler::with_goal(args.seed, args.goal.goal(args.board)?);
+    let puzzle_states: Vec<[i8; N]> = (0..args.count)
+        .map(|_| scrambler.scramble(args.scramble_mode()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| error.to_string())?;
+
+    match args.format {
+        Format::Json => {
+            let puzzles = puzzle_states
+                .iter()
+                .map(|puzzle_state| puzzle_state.iter().map(|tile| tile_label::<N>(*tile)).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let output = json!({
+                "seed": args.seed,
+                "board": args.board,
+                "mode": format!("{:?}", args.mode).to_lowercase(),
+                "moves": args.moves,
+                "puzzles": puzzles,
+            });
+            println!("{}", output);
+        }
+        Format::Text if args.count == 1 => println!("{}\n\n{}", format_puzzle_state(&puzzle_states[0]), draw_puzzle_state(&puzzle_states[0], &args.board)),
+        Format::Text => puzzle_states.iter().for_each(|puzzle_state| println!("{}", format_puzzle_state(puzzle_state))),
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,389 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::{fs, thread};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use algo::{draw_puzzle_state, movable_piece, parse_puzzle_state, replay_steps, tile_label, Renderer, AStar, BidirectionalAStar, Board, Checkpoint, Goal, HdaStar, Heuristic, IdaStar, MoveNotation, PatternDatabase, Portfolio, SmaStar, TieBreaking, SearchLimits, SearchStats, Solve, SolveError, SolvedState};
+use clap::{Args, ValueEnum};
+use crossterm::{cursor, queue, style, terminal};
+use serde_json::json;
+
+use super::{parse_seconds, with_tile_count, Format, Notation, PuzzleArgs};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum SolverKind {
+    /// A* with an open and closed list, fast but keeps every visited state in memory
+    #[value(name = "astar")]
+    AStar,
+    /// Iterative deepening A*, slower but only keeps the current path in memory
+    #[value(name = "idastar")]
+    IdaStar,
+    /// Hash distributed A*, runs A* on --threads threads that each own part of the states
+    #[value(name = "hdastar")]
+    HdaStar,
+    /// Bidirectional A*, searches from both ends and stops once the halves can't get any shorter
+    Bidirectional,
+    /// Simplified memory-bounded A*, forgets the least promising nodes once it holds --max-nodes nodes
+    #[value(name = "smastar")]
+    SmaStar,
+    /// Races astar and idastar on their own threads and takes the first answer
+    Portfolio,
+}
+
+impl SolverKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SolverKind::AStar => "astar",
+            SolverKind::IdaStar => "idastar",
+            SolverKind::HdaStar => "hdastar",
+            SolverKind::Bidirectional => "bidirectional",
+            SolverKind::SmaStar => "smastar",
+            SolverKind::Portfolio => "portfolio",
+        }
+    }
+}
+
+/// The solver and the limits it works within.
+#[derive(Debug, Args)]
+pub struct SolverArgs {
+    /// The search algorithm
+    #[arg(short, long, value_enum, default_value_t = SolverKind::AStar)]
+    pub solver: SolverKind,
+
+    /// The estimate of the remaining moves: manhattan, misplaced-tiles or linear-conflict
+    #[arg(short = 'H', long, default_value_t = Heuristic::Manhattan)]
+    pub heuristic: Heuristic,
+
+    /// The order of the astar open nodes with the same value: higher-length, lower-estimate, lifo or fifo
+    #[arg(long, default_value_t = TieBreaking::Lifo)]
+    pub tie_breaking: TieBreaking,
+
+    /// Give up after expanding this many nodes
+    #[arg(long)]
+    pub max_expanded: Option<u64>,
+
+    /// Give up after this many seconds
+    #[arg(long, value_parser = parse_seconds)]
+    pub time_limit: Option<Duration>,
+
+    /// The number of threads of the hdastar solver, one for every thread the machine can run at once when left out
+    #[arg(short = 't', long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub threads: Option<u16>,
+
+    /// The number of nodes the smastar solver keeps in memory, 100000 when left out
+    #[arg(long, value_parser = clap::value_parser!(u64).range(2..))]
+    pub max_nodes: Option<u64>,
+}
+
+impl SolverArgs {
+    pub fn limits(&self) -> SearchLimits {
+        SearchLimits {
+            max_expanded: self.max_expanded,
+            time_limit: self.time_limit,
+            ..SearchLimits::none()
+        }
+    }
+
+    pub fn create<const N: usize>(&self, goal: Goal<N>) -> Box<dyn Solve<N>> {
+        let (heuristic, limits) = (self.heuristic, self.limits());
+        match self.solver {
+            SolverKind::AStar => Box::new(AStar::with_goal(goal).with_heuristic(heuristic).with_tie_breaking(self.tie_breaking).with_limits(limits)),
+            SolverKind::IdaStar => Box::new(IdaStar::with_goal(goal).with_heuristic(heuristic).with_limits(limits)),
+            SolverKind::HdaStar => {
+                let solver: HdaStar<N> = HdaStar::with_goal(goal).with_heuristic(heuristic).with_limits(limits);
+                match self.threads {
+                    Some(threads) => Box::new(solver.with_threads(threads as usize)),
+                    None => Box::new(solver),
+                }
+            }
+            SolverKind::Bidirectional => Box::new(BidirectionalAStar::with_goal(goal).with_heuristic(heuristic).with_limits(limits)),
+            SolverKind::SmaStar => {
+                let solver: SmaStar<N> = SmaStar::with_goal(goal).with_heuristic(heuristic).with_limits(limits);
+                match self.max_nodes {
+                    Some(max_nodes) => Box::new(solver.with_max_nodes(max_nodes as usize)),
+                    None => Box::new(solver),
+                }
+            }
+            SolverKind::Portfolio => Box::new(self.create_portfolio(goal)),
+        }
+    }
+
+    /// Creates the portfolio of the sequential solvers, with the time limit as its deadline.
+    pub fn create_portfolio<const N: usize>(&self, goal: Goal<N>) -> Portfolio<N> {
+        let (heuristic, tie_breaking, limits) = (self.heuristic, self.tie_breaking, self.limits());
+        let a_star_goal: Goal<N> = goal.clone();
+        let a_star_limits: SearchLimits = limits.clone();
+        let ida_star_limits: SearchLimits = limits.clone();
+        let portfolio: Portfolio<N> = Portfolio::new()
+            .with_solver("astar", move |portfolio_limits: SearchLimits| {
+                let limits: SearchLimits = SearchLimits { cancel: portfolio_limits.cancel, ..a_star_limits.clone() };
+                AStar::with_goal(a_star_goal.clone()).with_heuristic(heuristic).with_tie_breaking(tie_breaking).with_limits(limits)
+            })
+            .with_solver("idastar", move |portfolio_limits: SearchLimits| {
+                let limits: SearchLimits = SearchLimits { cancel: portfolio_limits.cancel, ..ida_star_limits.clone() };
+                IdaStar::with_goal(goal.clone()).with_heuristic(heuristic).with_limits(limits)
+            });
+        match limits.time_limit {
+            Some(time_limit) => portfolio.with_deadline(time_limit),
+            None => portfolio,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct SolveArgs {
+    #[command(flatten)]
+    pub puzzle: PuzzleArgs,
+
+    #[command(flatten)]
+    pub solver: SolverArgs,
+
+    /// How the moves are written
+    #[arg(short, long, value_enum, default_value_t = Notation::Blank)]
+    pub notation: Notation,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// Also solve with the sequential A* and report its nodes and how much faster the chosen solver was
+    #[arg(long)]
+    pub speedup: bool,
+
+    /// Also estimate the idastar moves with a pattern database of these tiles, e.g. 1,2,3
+    #[arg(long, value_delimiter = ',')]
+    pub pattern: Vec<usize>,
+
+    /// Write idastar checkpoints to this file, and resume from it when it exists; it is removed once solved
+    #[arg(long)]
+    pub checkpoint: Option<PathBuf>,
+
+    /// The number of seconds between checkpoints
+    #[arg(long, default_value = "60", value_parser = parse_seconds, requires = "checkpoint")]
+    pub checkpoint_interval: Duration,
+
+    /// Replay the solution in the terminal, one move every --delay milliseconds
+    #[arg(long)]
+    pub animate: bool,
+
+    /// The milliseconds between the moves of the replay
+    #[arg(long, default_value_t = 300, requires = "animate")]
+    pub delay: u64,
+
+    /// Write images of the solution to this directory
+    #[arg(long)]
+    pub export: Option<PathBuf>,
+
+    /// An image of every step, or all steps on a single sprite sheet
+    #[arg(long, value_enum, default_value_t = ImageFormat::Svg, requires = "export")]
+    pub image_format: ImageFormat,
+
+    /// The width and height of a tile in the images, in pixels
+    #[arg(long, default_value_t = Renderer::DEFAULT_TILE_SIZE as u16, value_parser = clap::value_parser!(u16).range(1..), requires = "export")]
+    pub tile_size: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ImageFormat {
+    /// step-000.svg, step-001.svg, ...
+    Svg,
+    /// step-000.ppm, step-001.ppm, ...
+    Ppm,
+    /// solution.svg with every step in rows of eight
+    SpriteSheet,
+}
+
+pub fn run(args: &SolveArgs) -> Result<ExitCode, String> {
+    let (text, board) = args.puzzle.read()?;
+    with_tile_count!(board.len(), solve(args, board, &text))
+}
+
+fn solve<const N: usize>(args: &SolveArgs, board: Board, text: &str) -> Result<ExitCode, String> {
+    let mut puzzle_state: [i8; N] = parse_puzzle_state(text).map_err(|error| error.to_string())?;
+    let start: [i8; N] = puzzle_state;
+    let goal: Goal<N> = args.puzzle.goal.goal(board)?;
+    let notation: MoveNotation = args.notation.into();
+
+    if !args.pattern.is_empty() && args.solver.solver != SolverKind::IdaStar {
+        return Err("only the idastar solver looks up a pattern database".to_string());
+    }
+    let (result, winner) = match (args.solver.solver, &args.checkpoint) {
+        (SolverKind::IdaStar, Some(path)) => (solve_with_checkpoints(args, ida_star(args, goal)?, path, &mut puzzle_state)?, None),
+        (_, Some(_)) => return Err("only the idastar solver writes checkpoints".to_string()),
+        (SolverKind::IdaStar, None) => (ida_star(args, goal)?.solve(&mut puzzle_state), None),
+        (SolverKind::Portfolio, None) => {
+            let mut portfolio: Portfolio<N> = args.solver.create_portfolio(goal);
+            (portfolio.solve(&mut puzzle_state), portfolio.winner().map(str::to_string))
+        }
+        (_, None) => (args.solver.create(goal).solve(&mut puzzle_state), None),
+    };
+    // the sequential run gets the same limits as the chosen solver, and no speedup is reported when it reaches them
+    let sequential: Option<SearchStats> = match (&result, args.speedup) {
+        (Ok(_), true) => AStar::with_goal(args.puzzle.goal.goal(board)?)
+            .with_heuristic(args.solver.heuristic)
+            .with_tie_breaking(args.solver.tie_breaking)
+            .with_limits(args.solver.limits())
+            .solve(&mut start.clone())
+            .ok()
+            .map(|solved_state| solved_state.stats),
+        _ => None,
+    };
+    if let (Ok(solved_state), Some(directory)) = (&result, &args.export) {
+        export(args, board, &start, solved_state, directory)?;
+    }
+    let labels: Vec<usize> = start.iter().map(|tile| tile_label::<N>(*tile)).collect::<Vec<_>>();
+    match args.format {
+        Format::Json => {
+            let mut output = json!({
+                "board": board,
+                "start": labels,
+                "solver": args.solver.solver.name(),
+                "heuristic": args.solver.heuristic.name(),
+            });
+            match &result {
+                Ok(solved_state) => {
+                    output["status"] = json!("solved");
+                    output["moves"] = json!(notation.format(&solved_state.moves));
+                    output["length"] = json!(solved_state.moves.len());
+                    output["stats"] = json!(solved_state.stats);
+                    if let Some(winner) = &winner {
+                        output["winner"] = json!(winner);
+                    }
+                    if let Some(sequential) = &sequential {
+                        output["sequential"] = json!({
+                            "stats": sequential,
+                            "speedup": speedup(sequential, &solved_state.stats),
+                            "expanded_ratio": expanded_ratio(sequential, &solved_state.stats),
+                        });
+                    }
+                }
+                Err(SolveError::Unsolvable) => output["status"] = json!("unsolvable"),
+                Err(SolveError::CheckpointMismatch) => output["status"] = json!("checkpoint-mismatch"),
+                Err(SolveError::LimitReached(stats)) => {
+                    output["status"] = json!("limit-reached");
+                    output["stats"] = json!(stats);
+                }
+            }
+            println!("{}", output);
+        }
+        Format::Text => {
+            if let (Ok(solved_state), true) = (&result, args.animate) {
+                animate(args, board, &start, solved_state).map_err(|error| error.to_string())?;
+            }
+            println!("{}", draw_puzzle_state(&start, &board));
+            println!();
+            match &result {
+                Ok(solved_state) => {
+                    println!("Solved in {} moves: {}", solved_state.moves.len(), notation.format(&solved_state.moves));
+                    println!("{}", stats_text(&solved_state.stats));
+                    if let Some(winner) = &winner {
+                        println!("Won by {}", winner);
+                    }
+                    if let Some(sequential) = &sequential {
+                        println!("Sequential A*: {}", stats_text(sequential));
+                        println!("Speedup {:.2}x over sequential A*", speedup(sequential, &solved_state.stats));
+                        println!(
+                            "Expanded {} nodes against {} of sequential A*, {:.2}x as many",
+                            solved_state.stats.expanded,
+                            sequential.expanded,
+                            expanded_ratio(sequential, &solved_state.stats)
+                        );
+                    }
+                }
+                Err(error) => println!("Not solved, {}", error),
+            }
+        }
+    }
+
+    Ok(if result.is_ok() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+/// Creates the idastar solver, looking up the pattern database of --pattern when it is given.
+fn ida_star<const N: usize>(args: &SolveArgs, goal: Goal<N>) -> Result<IdaStar<N>, String> {
+    let mut solver: IdaStar<N> = IdaStar::with_goal(goal.clone()).with_heuristic(args.solver.heuristic).with_limits(args.solver.limits());
+    if !args.pattern.is_empty() {
+        let tiles: Vec<i8> = args
+            .pattern
+            .iter()
+            .map(|label| match *label {
+                0 => Ok(movable_piece(N)),
+                label if label < N => Ok(label as i8 - 1),
+                label => Err(format!("the pattern tile {} is not part of the puzzle", label)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let pattern_database: PatternDatabase<N> = PatternDatabase::new(&goal, &tiles).ok_or_else(|| format!("a pattern database of {} tiles is too large for this board", args.pattern.len()))?;
+        solver = solver.with_pattern_database(Arc::new(pattern_database));
+    }
+    Ok(solver)
+}
+
+/// Solves with IDA*, resuming from the checkpoint file when there is one and removing it once solved.
+fn solve_with_checkpoints<const N: usize>(args: &SolveArgs, solver: IdaStar<N>, path: &PathBuf, puzzle_state: &mut [i8; N]) -> Result<Result<SolvedState<N>, SolveError>, String> {
+    let mut solver: IdaStar<N> = solver.with_checkpoints(path, args.checkpoint_interval);
+    if path.exists() {
+        let checkpoint: Checkpoint<N> = Checkpoint::read(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+        if !solver.can_resume(&checkpoint, puzzle_state) {
+            return Err(format!("{} was written for another puzzle state, goal, heuristic or pattern", path.display()));
+        }
+        eprintln!("Resuming from {} at bound {} after {} expanded nodes", path.display(), checkpoint.bound, checkpoint.stats.expanded);
+        solver = solver.with_resume(checkpoint);
+    }
+
+    let result: Result<SolvedState<N>, SolveError> = solver.solve(puzzle_state);
+    if let Some(error) = solver.checkpoint_error() {
+        eprintln!("warning: could not write a checkpoint to {}: {}", path.display(), error);
+    }
+    if result.is_ok() {
+        std::fs::remove_file(path).ok();
+    }
+    Ok(result)
+}
+
+/// Writes the images of every step of the solution to the directory.
+fn export<const N: usize>(args: &SolveArgs, board: Board, start: &[i8; N], solved_state: &SolvedState<N>, directory: &Path) -> Result<(), String> {
+    let steps: Vec<[i8; N]> = replay_steps(&board, start, &solved_state.moves).map_err(|error| error.to_string())?;
+    let renderer: Renderer = Renderer::new(board).with_tile_size(args.tile_size as usize);
+    let write = |name: String, contents: &[u8]| {
+        let path: PathBuf = directory.join(name);
+        fs::write(&path, contents).map_err(|error| format!("{}: {}", path.display(), error))
+    };
+    fs::create_dir_all(directory).map_err(|error| format!("{}: {}", directory.display(), error))?;
+    match args.image_format {
+        ImageFormat::Svg => steps.iter().enumerate().try_for_each(|(step, puzzle_state)| write(format!("step-{:03}.svg", step), renderer.svg(puzzle_state).as_bytes())),
+        ImageFormat::Ppm => steps.iter().enumerate().try_for_each(|(step, puzzle_state)| write(format!("step-{:03}.ppm", step), &renderer.ppm(puzzle_state))),
+        ImageFormat::SpriteSheet => write("solution.svg".to_string(), renderer.sprite_sheet(&steps, &solved_state.moves, 8).as_bytes()),
+    }
+}
+
+/// Replays the solution in place in the terminal, redrawing the board after every move.
+fn animate<const N: usize>(args: &SolveArgs, board: Board, start: &[i8; N], solved_state: &SolvedState<N>) -> io::Result<()> {
+    let steps: Vec<[i8; N]> = replay_steps(&board, start, &solved_state.moves).map_err(io::Error::other)?;
+    let notation: MoveNotation = args.notation.into();
+    let mut stdout = io::stdout();
+    for (step, puzzle_state) in steps.iter().enumerate() {
+        let caption: String = match step.checked_sub(1) {
+            Some(index) => format!("Move {}/{}: {}", step, solved_state.moves.len(), notation.format(&solved_state.moves[index..=index])),
+            None => format!("Start, {} moves to go", solved_state.moves.len()),
+        };
+        queue!(stdout, cursor::MoveTo(0, 0), terminal::Clear(terminal::ClearType::All), style::Print(draw_puzzle_state(puzzle_state, &board)), style::Print("\n\n"), style::Print(caption), style::Print("\n"))?;
+        stdout.flush()?;
+        thread::sleep(Duration::from_millis(args.delay));
+    }
+    println!();
+    Ok(())
+}
+
+/// Gets how many times faster the search was than the sequential one.
+fn speedup(sequential: &SearchStats, stats: &SearchStats) -> f64 {
+    sequential.elapsed.as_secs_f64() / stats.elapsed.as_secs_f64().max(f64::MIN_POSITIVE)
+}
+
+/// Gets how many nodes the search expanded for every node the sequential one expanded.
+fn expanded_ratio(sequential: &SearchStats, stats: &SearchStats) -> f64 {
+    stats.expanded as f64 / sequential.expanded.max(1) as f64
+}
+
+pub fn stats_text(stats: &SearchStats) -> String {
+    format!("Expanded {} and generated {} nodes in {:.3?}", stats.expanded, stats.generated, stats.elapsed)
+}
@@ -0,0 +1,60 @@
+use std::process::ExitCode;
+
+use algo::{format_puzzle_state, parse_puzzle_state, replay_moves, tile_label, Board, Direction, Goal, MoveNotation};
+use clap::Args;
+use serde_json::json;
+
+use super::{with_tile_count, Format, Notation, PuzzleArgs};
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    pub puzzle: PuzzleArgs,
+
+    /// The moves to replay, e.g. LURD
+    #[arg(short, long)]
+    pub moves: String,
+
+    /// How the moves are written
+    #[arg(short, long, value_enum, default_value_t = Notation::Blank)]
+    pub notation: Notation,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+pub fn run(args: &VerifyArgs) -> Result<ExitCode, String> {
+    let (text, board) = args.puzzle.read()?;
+    with_tile_count!(board.len(), verify(args, board, &text))
+}
+
+fn verify<const N: usize>(args: &VerifyArgs, board: Board, text: &str) -> Result<ExitCode, String> {
+    let puzzle_state: [i8; N] = parse_puzzle_state(text).map_err(|error| error.to_string())?;
+    let goal: Goal<N> = args.puzzle.goal.goal(board)?;
+    let moves: Vec<Direction> = MoveNotation::from(args.notation).parse(&args.moves).map_err(|error| error.to_string())?;
+    let labels = |puzzle_state: &[i8; N]| puzzle_state.iter().map(|tile| tile_label::<N>(*tile)).collect::<Vec<_>>();
+
+    let (is_solved, output, message) = match replay_moves(&board, &puzzle_state, &moves) {
+        Err(illegal_move) => (
+            false,
+            json!({ "status": "illegal-move", "index": illegal_move.index, "state": labels(&illegal_move.puzzle_state) }),
+            format!("Move {} ({}) takes the movable piece off the board in {}", illegal_move.index + 1, illegal_move.direction, format_puzzle_state(&illegal_move.puzzle_state)),
+        ),
+        Ok(final_state) if goal.is_reached(&final_state) => (
+            true,
+            json!({ "status": "solved", "length": moves.len(), "state": labels(&final_state) }),
+            format!("Solved in {} moves", moves.len()),
+        ),
+        Ok(final_state) => (
+            false,
+            json!({ "status": "not-solved", "length": moves.len(), "state": labels(&final_state) }),
+            format!("Not solved, the moves end in {}", format_puzzle_state(&final_state)),
+        ),
+    };
+
+    match args.format {
+        Format::Json => println!("{}", output),
+        Format::Text => println!("{}", message),
+    }
+    Ok(if is_solved { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
@@ -0,0 +1,276 @@
+use std::fmt;
+
+use crate::{replay_steps, verify_solution, BatchRunner, Direction, Goal, Instance, ScrambleError, ScrambleMode, Scrambler, Solve, SolveError, VerifyError, TOTAL_PUZZLE_SIZE};
+
+/// Creates a fresh solver for every puzzle state, on the worker thread that solves it.
+type CreateSolver<const N: usize> = Box<dyn Fn() -> Box<dyn Solve<N>> + Send + Sync>;
+
+struct Entry<const N: usize> {
+    name: String,
+    is_optimal: bool,
+    create_solver: CreateSolver<N>,
+}
+
+/// Something a solver got wrong on a puzzle state that reaches the goal.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum Discrepancy<const N: usize = TOTAL_PUZZLE_SIZE> {
+    /// The solver says the goal can't be reached.
+    Unsolvable { solver: String },
+    /// Replaying the moves of the solver on the puzzle state doesn't end where it should.
+    InvalidMoves { solver: String, error: VerifyError<N> },
+    /// An optimal solver found more moves than another solver, with the number of moves of every solver.
+    LengthMismatch { lengths: Vec<(String, usize)> },
+}
+
+impl<const N: usize> fmt::Display for Discrepancy<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Discrepancy::Unsolvable { solver } => write!(f, "{} says the goal cannot be reached", solver),
+            Discrepancy::InvalidMoves { solver, error } => write!(f, "the moves of {} are wrong, {}", solver, error),
+            Discrepancy::LengthMismatch { lengths } => {
+                let lengths: Vec<String> = lengths.iter().map(|(solver, length)| format!("{} {}", solver, length)).collect::<Vec<_>>();
+                write!(f, "the solvers disagree on the number of moves: {}", lengths.join(", "))
+            }
+        }
+    }
+}
+
+/// What every solver answered for a single puzzle state.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct InstanceCheck<const N: usize = TOTAL_PUZZLE_SIZE> {
+    pub puzzle_state: [i8; N],
+    /// The number of moves of every solver whose moves reach the goal.
+    pub lengths: Vec<(String, usize)>,
+    /// The solvers that gave up because of their limits, which is not held against them.
+    pub gave_up: Vec<String>,
+    pub discrepancies: Vec<Discrepancy<N>>,
+    /// The shortest moves that reach the goal, when any solver found them.
+    pub shortest_moves: Option<Vec<Direction>>,
+}
+
+impl<const N: usize> InstanceCheck<N> {
+    pub fn is_ok(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+/// A generated puzzle state the solvers got wrong.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct DifferentialFailure<const N: usize = TOTAL_PUZZLE_SIZE> {
+    /// The index of the puzzle state in `DifferentialReport::checks`.
+    pub index: usize,
+    /// The puzzle state closest to the goal on the shortest moves found that the solvers still get wrong.
+    pub minimal: InstanceCheck<N>,
+}
+
+/// The answers of the solvers for every generated puzzle state.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct DifferentialReport<const N: usize = TOTAL_PUZZLE_SIZE> {
+    pub seed: u64,
+    pub checks: Vec<InstanceCheck<N>>,
+    pub failures: Vec<DifferentialFailure<N>>,
+}
+
+impl<const N: usize> DifferentialReport<N> {
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Gets the smallest puzzle state of every failure as an instance, named after the seed and the
+    /// generated puzzle state it came from, ready for an instance file.
+    pub fn reproducing_instances(&self, goal: &Goal<N>) -> Vec<Instance> {
+        self.failures
+            .iter()
+            .map(|failure| Instance::new(Some(format!("seed-{}-{}", self.seed, failure.index + 1)), *goal.board(), &failure.minimal.puzzle_state))
+            .collect::<Vec<_>>()
+    }
+}
+
+/// Runs several solvers on random puzzle states generated from a seed and checks their answers
+/// against each other. Every move list is replayed on its puzzle state, and the solvers added as
+/// optimal have to find as few moves as the best answer of any solver.
+///
+/// When the solvers get a puzzle state wrong, it walks the shortest moves found towards the goal
+/// and keeps the state closest to the goal the solvers still get wrong, so the failure can be
+/// reproduced on a small instance. The puzzle states are spread over the workers of a `BatchRunner`.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{AStar, DifferentialTester, IdaStar, ScrambleMode};
+///
+/// let report = DifferentialTester::<9>::new(42)
+///     .with_instances(3)
+///     .with_scramble_mode(ScrambleMode::RandomWalk(20))
+///     .with_solver("astar", AStar::new)
+///     .with_solver("idastar", IdaStar::new)
+///     .run()
+///     .unwrap();
+///
+/// assert_eq!(report.checks.len(), 3);
+/// assert!(report.is_ok());
+/// ```
+pub struct DifferentialTester<const N: usize = TOTAL_PUZZLE_SIZE> {
+    seed: u64,
+    goal: Goal<N>,
+    instances: usize,
+    scramble_mode: ScrambleMode,
+    runner: BatchRunner,
+    entries: Vec<Entry<N>>,
+}
+
+impl<const N: usize> DifferentialTester<N> {
+    pub const DEFAULT_INSTANCES: usize = 100;
+
+    pub fn new(seed: u64) -> DifferentialTester<N> {
+        DifferentialTester::with_goal(seed, Goal::default())
+    }
+
+    /// The solvers have to be created for the same goal.
+    pub fn with_goal(seed: u64, goal: Goal<N>) -> DifferentialTester<N> {
+        DifferentialTester {
+            seed,
+            goal,
+            instances: Self::DEFAULT_INSTANCES,
+            scramble_mode: ScrambleMode::Shuffle,
+            runner: BatchRunner::default(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Sets the number of puzzle states to generate.
+    pub fn with_instances(mut self, instances: usize) -> DifferentialTester<N> {
+        self.instances = instances;
+        self
+    }
+
+    /// Sets how the puzzle states are scrambled, a full shuffle by default.
+    pub fn with_scramble_mode(mut self, scramble_mode: ScrambleMode) -> DifferentialTester<N> {
+        self.scramble_mode = scramble_mode;
+        self
+    }
+
+    pub fn with_runner(mut self, runner: BatchRunner) -> DifferentialTester<N> {
+        self.runner = runner;
+        self
+    }
+
+    /// Adds a solver that always finds a shortest solution.
+    pub fn with_solver<S, F>(self, name: &str, create_solver: F) -> DifferentialTester<N>
+    where
+        S: Solve<N> + 'static,
+        F: Fn() -> S + Send + Sync + 'static,
+    {
+        self.with_entry(name, true, create_solver)
+    }
+
+    /// Adds a solver whose solutions may be longer than needed, only its moves are checked.
+    pub fn with_inexact_solver<S, F>(self, name: &str, create_solver: F) -> DifferentialTester<N>
+    where
+        S: Solve<N> + 'static,
+        F: Fn() -> S + Send + Sync + 'static,
+    {
+        self.with_entry(name, false, create_solver)
+    }
+
+    fn with_entry<S, F>(mut self, name: &str, is_optimal: bool, create_solver: F) -> DifferentialTester<N>
+    where
+        S: Solve<N> + 'static,
+        F: Fn() -> S + Send + Sync + 'static,
+    {
+        self.entries.push(Entry {
+            name: name.to_string(),
+            is_optimal,
+            create_solver: Box::new(move || Box::new(create_solver())),
+        });
+        self
+    }
+
+    /// Generates the puzzle states, has every solver solve them and shrinks the ones they get wrong.
+    pub fn run(&self) -> Result<DifferentialReport<N>, ScrambleError> {
+        let mut scrambler: Scrambler<N> = Scrambler::with_goal(self.seed, self.goal.clone());
+        let puzzle_states: Vec<[i8; N]> = (0..self.instances).map(|_| scrambler.scramble(self.scramble_mode)).collect::<Result<Vec<_>, _>>()?;
+
+        let checks: Vec<(InstanceCheck<N>, Option<InstanceCheck<N>>)> = self
+            .runner
+            .run(&puzzle_states, |puzzle_state| {
+                let check: InstanceCheck<N> = self.check(puzzle_state);
+                let minimal: Option<InstanceCheck<N>> = (!check.is_ok()).then(|| self.shrink(&check));
+                (check, minimal)
+            })
+            .results;
+
+        let failures: Vec<DifferentialFailure<N>> = checks
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (_, minimal))| minimal.clone().map(|minimal| DifferentialFailure { index, minimal }))
+            .collect::<Vec<_>>();
+        Ok(DifferentialReport {
+            seed: self.seed,
+            checks: checks.into_iter().map(|(check, _)| check).collect::<Vec<_>>(),
+            failures,
+        })
+    }
+
+    /// Has every solver solve a puzzle state that reaches the goal and compares their answers.
+    pub fn check(&self, puzzle_state: &[i8; N]) -> InstanceCheck<N> {
+        let mut check: InstanceCheck<N> = InstanceCheck {
+            puzzle_state: *puzzle_state,
+            lengths: Vec::new(),
+            gave_up: Vec::new(),
+            discrepancies: Vec::new(),
+            shortest_moves: None,
+        };
+        let mut optimal_lengths: Vec<usize> = Vec::new();
+
+        for entry in &self.entries {
+            let solver = entry.name.clone();
+            match (entry.create_solver)().solve(&mut puzzle_state.clone()) {
+                Ok(solved_state) => match verify_solution(puzzle_state, &solved_state, &self.goal) {
+                    Ok(()) => {
+                        if entry.is_optimal {
+                            optimal_lengths.push(solved_state.moves.len());
+                        }
+                        if check.shortest_moves.as_ref().is_none_or(|moves| solved_state.moves.len() < moves.len()) {
+                            check.shortest_moves = Some(solved_state.moves.clone());
+                        }
+                        check.lengths.push((solver, solved_state.moves.len()));
+                    }
+                    Err(error) => check.discrepancies.push(Discrepancy::InvalidMoves { solver, error }),
+                },
+                Err(SolveError::Unsolvable) => check.discrepancies.push(Discrepancy::Unsolvable { solver }),
+                Err(SolveError::LimitReached(_) | SolveError::CheckpointMismatch) => check.gave_up.push(solver),
+            }
+        }
+
+        let shortest: Option<usize> = check.shortest_moves.as_ref().map(Vec::len);
+        if optimal_lengths.iter().any(|length| Some(*length) != shortest) {
+            check.discrepancies.push(Discrepancy::LengthMismatch { lengths: check.lengths.clone() });
+        }
+        check
+    }
+
+    /// Checks the puzzle states on the shortest moves found, from the goal outwards, and gets the
+    /// first one the solvers get wrong. Without any moves the puzzle state can't get smaller.
+    fn shrink(&self, check: &InstanceCheck<N>) -> InstanceCheck<N> {
+        let Some(moves) = &check.shortest_moves else {
+            return check.clone();
+        };
+        let steps: Vec<[i8; N]> = replay_steps(self.goal.board(), &check.puzzle_state, moves).expect("the moves were verified");
+        steps[1..]
+            .iter()
+            .rev()
+            .skip(1)
+            .map(|puzzle_state| self.check(puzzle_state))
+            .find(|smaller_check| !smaller_check.is_ok())
+            .unwrap_or_else(|| check.clone())
+    }
+}
@@ -1,3 +1,8 @@
+use std::fmt;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 #[derive(Debug)]
 #[derive(PartialEq)]
 #[derive(Clone, Copy)]
@@ -7,4 +12,148 @@ pub enum Direction {
     Up,
     Down,
     None
-}
\ No newline at end of file
+}
+
+impl Direction {
+    /// Every direction the movable piece can move in.
+    pub const MOVES: [Direction; 4] = [Direction::Left, Direction::Right, Direction::Up, Direction::Down];
+
+    /// Gets the direction that undoes this one.
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::None => Direction::None,
+        }
+    }
+
+    /// Gets the letter of the direction, `Direction::None` has no letter as it isn't a move.
+    pub fn to_char(&self) -> Option<char> {
+        match self {
+            Direction::Left => Some('L'),
+            Direction::Right => Some('R'),
+            Direction::Up => Some('U'),
+            Direction::Down => Some('D'),
+            Direction::None => None,
+        }
+    }
+
+    /// Gets the direction of a letter, ignoring its case.
+    pub fn from_char(letter: char) -> Option<Direction> {
+        match letter.to_ascii_uppercase() {
+            'L' => Some(Direction::Left),
+            'R' => Some(Direction::Right),
+            'U' => Some(Direction::Up),
+            'D' => Some(Direction::Down),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_char() {
+            Some(letter) => write!(f, "{}", letter),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Serialize for Direction {
+    /// Writes the letter of the direction, `Direction::None` as an empty string.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Direction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Direction, D::Error> {
+        let text: String = String::deserialize(deserializer)?;
+        let mut letters = text.chars();
+        match (letters.next(), letters.next()) {
+            (None, _) => Ok(Direction::None),
+            (Some(letter), None) => Direction::from_char(letter).ok_or_else(|| D::Error::custom(ParseMovesError { position: 0, letter })),
+            _ => Err(D::Error::custom(format!("'{}' is not a single direction", text))),
+        }
+    }
+}
+
+/// Tells whether the letters of a move string describe where the movable piece goes,
+/// or where the tile that slides into the movable piece goes.
+///
+/// A solver always returns the moves of the movable piece, the tile moves are their opposites.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum MoveNotation {
+    Blank,
+    Tile,
+}
+
+impl MoveNotation {
+    /// Writes the moves of the movable piece as a compact string of letters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algo::{Direction, MoveNotation};
+    ///
+    /// let moves = vec![Direction::Left, Direction::Up, Direction::Right, Direction::Down];
+    ///
+    /// assert_eq!(MoveNotation::Blank.format(&moves), "LURD");
+    /// assert_eq!(MoveNotation::Tile.format(&moves), "RDLU");
+    /// ```
+    pub fn format(&self, moves: &[Direction]) -> String {
+        moves.iter().map(|direction| self.convert(*direction).to_string()).collect::<String>()
+    }
+
+    /// Reads a string of letters into the moves of the movable piece.
+    /// Whitespace and commas between the letters are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algo::{Direction, MoveNotation};
+    ///
+    /// assert_eq!(MoveNotation::Tile.parse("r d").unwrap(), vec![Direction::Left, Direction::Up]);
+    /// assert!(MoveNotation::Blank.parse("LX").is_err());
+    /// ```
+    pub fn parse(&self, text: &str) -> Result<Vec<Direction>, ParseMovesError> {
+        text.chars()
+            .enumerate()
+            .filter(|(_, letter)| !letter.is_whitespace() && *letter != ',')
+            .map(|(position, letter)| match Direction::from_char(letter) {
+                Some(direction) => Ok(self.convert(direction)),
+                None => Err(ParseMovesError { position, letter }),
+            })
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    /// Converts between the moves of the movable piece and the notation, which is the same both ways.
+    fn convert(&self, direction: Direction) -> Direction {
+        match self {
+            MoveNotation::Blank => direction,
+            MoveNotation::Tile => direction.opposite(),
+        }
+    }
+}
+
+/// A letter in a move string that isn't a direction.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub struct ParseMovesError {
+    /// The index of the character in the move string.
+    pub position: usize,
+    pub letter: char,
+}
+
+impl fmt::Display for ParseMovesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' at position {} is not a move, expected one of L, R, U or D", self.letter, self.position)
+    }
+}
+
+impl std::error::Error for ParseMovesError {}
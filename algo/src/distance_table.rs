@@ -0,0 +1,191 @@
+use std::collections::VecDeque;
+
+use crate::node::movable_piece_location;
+use crate::{movable_piece, Direction, Goal, Symmetry, TOTAL_PUZZLE_SIZE};
+
+const UNREACHED: u8 = u8::MAX;
+
+/// The optimal number of moves from every puzzle state to a goal, found by a breadth-first
+/// search outwards from the goal. Every permutation of the tiles has a slot, so the table
+/// holds up to `N!` bytes and only fits puzzles of up to `DistanceTable::MAX_TILES` tiles.
+///
+/// When the goal is mirrored onto itself by a `Symmetry`, a state and its mirror need the same
+/// number of moves, so the table only keeps the states with the movable piece on or below the
+/// diagonal and looks the other states up by their mirror. That leaves `N! (w + 1) / 2w` slots
+/// on a board of width `w`, two thirds of the slots for the 8-puzzle.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{DistanceTable, Goal};
+///
+/// let distance_table: DistanceTable = DistanceTable::new(&Goal::default()).unwrap();
+///
+/// assert_eq!(distance_table.distance(&[5, 2, 3, 1, 8, 4, 6, 0, 7]), Some(22));
+/// assert_eq!(distance_table.size(), 241_920);
+/// ```
+pub struct DistanceTable<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    symmetry: Option<Symmetry<N>>,
+    /// The slot of every position of the movable piece the table keeps states for.
+    movable_piece_slots: [Option<usize>; N],
+    distances: Vec<u8>,
+}
+
+impl<const N: usize> DistanceTable<N> {
+    /// The most tiles a puzzle can have for its table to fit in memory, `10!` bytes is about 3.6 MB.
+    pub const MAX_TILES: usize = 10;
+
+    /// Builds the table for the goal, or gets `None` when the puzzle has more than `MAX_TILES` tiles.
+    pub fn new(goal: &Goal<N>) -> Option<DistanceTable<N>> {
+        if N > Self::MAX_TILES {
+            return None;
+        }
+        let symmetry: Option<Symmetry<N>> = Symmetry::diagonal(goal);
+        let board = goal.board();
+        let mut movable_piece_slots: [Option<usize>; N] = [None; N];
+        let mut slots: usize = 0;
+        for (position, slot) in movable_piece_slots.iter_mut().enumerate() {
+            if symmetry.is_none() || board.row(position) >= board.column(position) {
+                *slot = Some(slots);
+                slots += 1;
+            }
+        }
+        let mut distance_table: DistanceTable<N> = DistanceTable {
+            goal: goal.clone(),
+            symmetry,
+            movable_piece_slots,
+            distances: vec![UNREACHED; slots * factorial(N - 1)],
+        };
+        let mut queue: VecDeque<usize> = VecDeque::new();
+
+        // a partial goal is reached by many states, all of them start the search
+        for index in 0..distance_table.distances.len() {
+            if goal.is_reached(&distance_table.unrank(index)) {
+                distance_table.distances[index] = 0;
+                queue.push_back(index);
+            }
+        }
+
+        while let Some(index) = queue.pop_front() {
+            let distance: u8 = distance_table.distances[index];
+            let puzzle_state: [i8; N] = distance_table.unrank(index);
+            let movable_piece_location: usize = movable_piece_location(&puzzle_state);
+            for direction in Direction::MOVES {
+                if let Some(target) = goal.board().neighbour(movable_piece_location, direction) {
+                    let mut next_state: [i8; N] = puzzle_state;
+                    next_state.swap(movable_piece_location, target);
+                    // the mirrored move from the mirror of the state isn't searched, so a state with the
+                    // movable piece on the diagonal gets the distance of its mirror as well
+                    let mirrored_index: Option<usize> = match &distance_table.symmetry {
+                        Some(symmetry) if symmetry.mirror_position(target) == target => Some(distance_table.rank(&symmetry.mirror(&next_state))),
+                        _ => None,
+                    };
+                    for next_index in std::iter::once(distance_table.rank(&next_state)).chain(mirrored_index) {
+                        if distance_table.distances[next_index] == UNREACHED {
+                            distance_table.distances[next_index] = distance + 1;
+                            queue.push_back(next_index);
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(distance_table)
+    }
+
+    pub fn goal(&self) -> &Goal<N> {
+        &self.goal
+    }
+
+    /// Gets the symmetry the table uses to keep only half of the states, if the goal has one.
+    pub fn symmetry(&self) -> Option<&Symmetry<N>> {
+        self.symmetry.as_ref()
+    }
+
+    /// Gets the number of bytes the distances take.
+    pub fn size(&self) -> usize {
+        self.distances.len()
+    }
+
+    /// Gets the optimal number of moves to the goal, or `None` when the goal can't be reached.
+    pub fn distance(&self, puzzle_state: &[i8; N]) -> Option<usize> {
+        match self.distances[self.rank(puzzle_state)] {
+            UNREACHED => None,
+            distance => Some(distance as usize),
+        }
+    }
+
+    /// Gets the largest number of moves any puzzle state needs to reach the goal.
+    pub fn max_distance(&self) -> usize {
+        self.distances.iter().filter(|distance| **distance != UNREACHED).max().copied().unwrap_or(0) as usize
+    }
+
+    /// Gets every puzzle state that needs exactly the given number of moves, in the order of their permutations.
+    pub fn states_at(&self, distance: usize) -> impl Iterator<Item = [i8; N]> + '_ {
+        let mut states: Vec<[i8; N]> = Vec::new();
+        for (index, _) in self.distances.iter().enumerate().filter(|(_, d)| **d != UNREACHED && **d as usize == distance) {
+            let puzzle_state: [i8; N] = self.unrank(index);
+            states.push(puzzle_state);
+            // the mirror of a state is kept itself when its movable piece is on the diagonal
+            if let Some(symmetry) = &self.symmetry {
+                let mirrored_state: [i8; N] = symmetry.mirror(&puzzle_state);
+                if self.movable_piece_slots[movable_piece_location(&mirrored_state)].is_none() {
+                    states.push(mirrored_state);
+                }
+            }
+        }
+        states.sort_by_cached_key(|puzzle_state| rank_tiles(puzzle_state));
+        states.into_iter()
+    }
+
+    /// Gets the slot of the puzzle state, or of its mirror when the table doesn't keep the state itself.
+    fn rank(&self, puzzle_state: &[i8; N]) -> usize {
+        let movable_piece_location: usize = movable_piece_location(puzzle_state);
+        let (puzzle_state, slot) = match (self.movable_piece_slots[movable_piece_location], &self.symmetry) {
+            (Some(slot), _) => (*puzzle_state, slot),
+            (None, Some(symmetry)) => {
+                let mirrored_state: [i8; N] = symmetry.mirror(puzzle_state);
+                (mirrored_state, self.movable_piece_slots[symmetry.mirror_position(movable_piece_location)].unwrap())
+            }
+            (None, None) => unreachable!("without a symmetry every position has a slot"),
+        };
+        let tiles: Vec<i8> = puzzle_state.iter().copied().filter(|tile| *tile != movable_piece(N)).collect::<Vec<_>>();
+        slot * factorial(N - 1) + rank_tiles(&tiles)
+    }
+
+    fn unrank(&self, index: usize) -> [i8; N] {
+        let slot: usize = index / factorial(N - 1);
+        let movable_piece_location: usize = self.movable_piece_slots.iter().position(|position_slot| *position_slot == Some(slot)).unwrap();
+        let mut tiles = unrank_tiles(index % factorial(N - 1), N - 1).into_iter();
+        core::array::from_fn(|position: usize| match position == movable_piece_location {
+            true => movable_piece(N),
+            false => tiles.next().unwrap(),
+        })
+    }
+}
+
+fn factorial(n: usize) -> usize {
+    (1..=n).product()
+}
+
+/// Numbers the permutations of `0..len` from `0` to `len! - 1` using their Lehmer code.
+fn rank_tiles(tiles: &[i8]) -> usize {
+    let mut index: usize = 0;
+    for i in 0..tiles.len() {
+        let smaller_after: usize = tiles[i + 1..].iter().filter(|tile| **tile < tiles[i]).count();
+        index = index * (tiles.len() - i) + smaller_after;
+    }
+    index
+}
+
+fn unrank_tiles(mut index: usize, len: usize) -> Vec<i8> {
+    let mut lehmer_code: Vec<usize> = vec![0; len];
+    for i in (0..len).rev() {
+        let base: usize = len - i;
+        lehmer_code[i] = index % base;
+        index /= base;
+    }
+    let mut tiles: Vec<i8> = (0..len as i8).collect::<Vec<_>>();
+    lehmer_code.into_iter().map(|code| tiles.remove(code)).collect::<Vec<_>>()
+}
@@ -0,0 +1,350 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::node::movable_piece_location;
+use crate::{movable_piece, Direction, Goal, TOTAL_PUZZLE_SIZE, WILDCARD};
+
+/// The bits a position takes in a packed state, enough for the tiles of a 5x5 board and a wildcard.
+const BITS_PER_POSITION: usize = 5;
+const PACKED_WILDCARD: u128 = (1 << BITS_PER_POSITION) - 1;
+
+/// A breadth-first search over every puzzle state that can reach a goal, for state spaces that
+/// don't fit in memory. The positions of a partial goal that hold a `WILDCARD` hold tiles that
+/// can't be told apart, so the search covers the abstraction of the puzzle to the placed tiles.
+///
+/// Every layer is written to the directory as a sorted file of packed states. The successors
+/// of a layer are sorted in runs of `buffer_states` states, which are merged while dropping the
+/// states of the two layers before, as a move can only lead back to one of those.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Board, ExternalBfs, Goal};
+///
+/// let directory = std::env::temp_dir().join("algo-external-bfs-doctest");
+/// let report = ExternalBfs::new(Goal::<6>::blank_last(Board::new(3, 2)), &directory).run().unwrap();
+///
+/// assert_eq!(report.total(), 360);
+/// assert_eq!(report.max_depth(), 21);
+/// # std::fs::remove_dir_all(directory).unwrap();
+/// ```
+pub struct ExternalBfs<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    directory: PathBuf,
+    buffer_states: usize,
+    examples: usize,
+    keep_layers: bool,
+}
+
+/// What a breadth-first search found out about the state space.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct BfsReport<const N: usize = TOTAL_PUZZLE_SIZE> {
+    /// The number of states at every distance from the goal.
+    pub layer_sizes: Vec<u64>,
+    /// Some of the states furthest from the goal.
+    pub antipodes: Vec<[i8; N]>,
+}
+
+/// Reasons a breadth-first search can't run.
+#[derive(Debug)]
+pub enum ExternalBfsError {
+    Io(io::Error),
+    /// The goal doesn't place the movable piece, so the search can't tell which tiles move.
+    NoMovablePiece,
+}
+
+impl fmt::Display for ExternalBfsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExternalBfsError::Io(error) => write!(f, "{}", error),
+            ExternalBfsError::NoMovablePiece => write!(f, "the goal has to place the movable piece"),
+        }
+    }
+}
+
+impl std::error::Error for ExternalBfsError {}
+
+impl From<io::Error> for ExternalBfsError {
+    fn from(error: io::Error) -> ExternalBfsError {
+        ExternalBfsError::Io(error)
+    }
+}
+
+impl<const N: usize> BfsReport<N> {
+    /// Gets the number of moves the furthest states need to reach the goal.
+    pub fn max_depth(&self) -> usize {
+        self.layer_sizes.len() - 1
+    }
+
+    /// Gets the number of states that can reach the goal.
+    pub fn total(&self) -> u64 {
+        self.layer_sizes.iter().sum()
+    }
+}
+
+impl<const N: usize> ExternalBfs<N> {
+    /// The number of states sorted in memory at once when no other size is given, 64 MB worth of states.
+    pub const DEFAULT_BUFFER_STATES: usize = 1 << 22;
+
+    /// # Panics
+    ///
+    /// Panics when the puzzle has more than 25 tiles.
+    pub fn new(goal: Goal<N>, directory: impl Into<PathBuf>) -> ExternalBfs<N> {
+        assert!(N * BITS_PER_POSITION <= 128, "a state of {} tiles doesn't fit in a packed state", N);
+        ExternalBfs {
+            goal,
+            directory: directory.into(),
+            buffer_states: Self::DEFAULT_BUFFER_STATES,
+            examples: 5,
+            keep_layers: false,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics when the buffer can't hold a state.
+    pub fn with_buffer_states(mut self, buffer_states: usize) -> ExternalBfs<N> {
+        assert!(buffer_states > 0, "the buffer needs room for at least 1 state");
+        self.buffer_states = buffer_states;
+        self
+    }
+
+    /// Sets the number of states furthest from the goal the report holds.
+    pub fn with_examples(mut self, examples: usize) -> ExternalBfs<N> {
+        self.examples = examples;
+        self
+    }
+
+    /// Keeps the file of every layer, instead of removing the layers once they can no longer hold duplicates.
+    pub fn with_keep_layers(mut self, keep_layers: bool) -> ExternalBfs<N> {
+        self.keep_layers = keep_layers;
+        self
+    }
+
+    /// Gets the file the layer at the given distance is written to.
+    pub fn layer_path(&self, depth: usize) -> PathBuf {
+        self.directory.join(format!("layer-{:03}.bin", depth))
+    }
+
+    pub fn run(&self) -> Result<BfsReport<N>, ExternalBfsError> {
+        self.run_with_progress(|_, _| {})
+    }
+
+    /// Runs the search, calling `progress` with the distance and the size of every layer once it is written.
+    pub fn run_with_progress<F>(&self, mut progress: F) -> Result<BfsReport<N>, ExternalBfsError>
+    where
+        F: FnMut(usize, u64),
+    {
+        if self.goal.position_of(movable_piece(N)).is_none() {
+            return Err(ExternalBfsError::NoMovablePiece);
+        }
+        fs::create_dir_all(&self.directory)?;
+
+        let mut writer: LayerWriter = LayerWriter::create(&self.layer_path(0), packed_len(N))?;
+        writer.write(pack(self.goal.puzzle_state()))?;
+        writer.finish()?;
+        let mut layer_sizes: Vec<u64> = vec![1];
+        progress(0, 1);
+
+        loop {
+            let depth: usize = layer_sizes.len();
+            let size: u64 = self.expand(depth)?;
+            if !self.keep_layers && depth >= 2 {
+                fs::remove_file(self.layer_path(depth - 2))?;
+            }
+            if size == 0 {
+                fs::remove_file(self.layer_path(depth))?;
+                break;
+            }
+            layer_sizes.push(size);
+            progress(depth, size);
+        }
+
+        let max_depth: usize = layer_sizes.len() - 1;
+        let mut reader: LayerReader = LayerReader::open(&self.layer_path(max_depth), packed_len(N))?;
+        let mut antipodes: Vec<[i8; N]> = Vec::new();
+        while antipodes.len() < self.examples {
+            match reader.next()? {
+                Some(packed) => antipodes.push(unpack(packed)),
+                None => break,
+            }
+        }
+        if !self.keep_layers {
+            fs::remove_file(self.layer_path(max_depth))?;
+        }
+
+        Ok(BfsReport { layer_sizes, antipodes })
+    }
+
+    /// Writes the layer at the given distance from the layer before it, getting its size.
+    fn expand(&self, depth: usize) -> Result<u64, ExternalBfsError> {
+        let packed_len: usize = packed_len(N);
+        let mut runs: Vec<PathBuf> = Vec::new();
+        let mut buffer: Vec<u128> = Vec::new();
+        let mut reader: LayerReader = LayerReader::open(&self.layer_path(depth - 1), packed_len)?;
+        while let Some(packed) = reader.next()? {
+            let puzzle_state: [i8; N] = unpack(packed);
+            let movable_piece_location: usize = movable_piece_location(&puzzle_state);
+            for direction in Direction::MOVES {
+                let Some(target) = self.goal.board().neighbour(movable_piece_location, direction) else {
+                    continue;
+                };
+                let mut next_state: [i8; N] = puzzle_state;
+                next_state.swap(movable_piece_location, target);
+                buffer.push(pack(&next_state));
+                if buffer.len() == self.buffer_states {
+                    runs.push(self.write_run(depth, runs.len(), &mut buffer)?);
+                }
+            }
+        }
+        if !buffer.is_empty() {
+            runs.push(self.write_run(depth, runs.len(), &mut buffer)?);
+        }
+
+        // merge the runs, dropping repeated states and the states of the two layers before
+        let mut readers: Vec<LayerReader> = runs.iter().map(|run| LayerReader::open(run, packed_len)).collect::<Result<Vec<_>, _>>()?;
+        let mut heap: BinaryHeap<Reverse<(u128, usize)>> = BinaryHeap::new();
+        for (index, reader) in readers.iter_mut().enumerate() {
+            if let Some(packed) = reader.next()? {
+                heap.push(Reverse((packed, index)));
+            }
+        }
+        let mut previous_layers: Vec<PeekingReader> = (depth.saturating_sub(2)..depth)
+            .map(|previous_depth| LayerReader::open(&self.layer_path(previous_depth), packed_len).map(PeekingReader::new))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut writer: LayerWriter = LayerWriter::create(&self.layer_path(depth), packed_len)?;
+        let mut last: Option<u128> = None;
+        while let Some(Reverse((packed, index))) = heap.pop() {
+            if let Some(next) = readers[index].next()? {
+                heap.push(Reverse((next, index)));
+            }
+            if last == Some(packed) {
+                continue;
+            }
+            last = Some(packed);
+            let mut is_duplicate: bool = false;
+            for previous_layer in previous_layers.iter_mut() {
+                is_duplicate |= previous_layer.contains(packed)?;
+            }
+            if !is_duplicate {
+                writer.write(packed)?;
+            }
+        }
+        let size: u64 = writer.finish()?;
+
+        for run in runs {
+            fs::remove_file(run)?;
+        }
+        Ok(size)
+    }
+
+    fn write_run(&self, depth: usize, index: usize, buffer: &mut Vec<u128>) -> Result<PathBuf, ExternalBfsError> {
+        buffer.sort_unstable();
+        buffer.dedup();
+        let path: PathBuf = self.directory.join(format!("layer-{:03}-run-{}.bin", depth, index));
+        let mut writer: LayerWriter = LayerWriter::create(&path, packed_len(N))?;
+        for packed in buffer.drain(..) {
+            writer.write(packed)?;
+        }
+        writer.finish()?;
+        Ok(path)
+    }
+}
+
+/// Gets the number of bytes a packed state of `len` positions takes on disk.
+fn packed_len(len: usize) -> usize {
+    (len * BITS_PER_POSITION).div_ceil(8)
+}
+
+/// Packs the state into a number that sorts like the state, with every position taking 5 bits.
+fn pack<const N: usize>(puzzle_state: &[i8; N]) -> u128 {
+    puzzle_state.iter().fold(0, |packed, tile| {
+        let value: u128 = if *tile == WILDCARD { PACKED_WILDCARD } else { *tile as u128 };
+        (packed << BITS_PER_POSITION) | value
+    })
+}
+
+fn unpack<const N: usize>(packed: u128) -> [i8; N] {
+    core::array::from_fn(|position: usize| {
+        let value: u128 = (packed >> ((N - 1 - position) * BITS_PER_POSITION)) & PACKED_WILDCARD;
+        if value == PACKED_WILDCARD { WILDCARD } else { value as i8 }
+    })
+}
+
+/// Writes packed states big-endian, so the files sort like the states.
+struct LayerWriter {
+    writer: BufWriter<File>,
+    packed_len: usize,
+    len: u64,
+}
+
+impl LayerWriter {
+    fn create(path: &Path, packed_len: usize) -> io::Result<LayerWriter> {
+        Ok(LayerWriter { writer: BufWriter::new(File::create(path)?), packed_len, len: 0 })
+    }
+
+    fn write(&mut self, packed: u128) -> io::Result<()> {
+        self.len += 1;
+        self.writer.write_all(&packed.to_be_bytes()[16 - self.packed_len..])
+    }
+
+    /// Flushes the file, getting the number of states written.
+    fn finish(mut self) -> io::Result<u64> {
+        self.writer.flush()?;
+        Ok(self.len)
+    }
+}
+
+struct LayerReader {
+    reader: BufReader<File>,
+    packed_len: usize,
+}
+
+impl LayerReader {
+    fn open(path: &Path, packed_len: usize) -> io::Result<LayerReader> {
+        Ok(LayerReader { reader: BufReader::new(File::open(path)?), packed_len })
+    }
+
+    fn next(&mut self) -> io::Result<Option<u128>> {
+        let mut bytes: [u8; 16] = [0; 16];
+        match self.reader.read_exact(&mut bytes[16 - self.packed_len..]) {
+            Ok(()) => Ok(Some(u128::from_be_bytes(bytes))),
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// Walks a sorted layer alongside the increasing states of a merge.
+struct PeekingReader {
+    reader: LayerReader,
+    current: Option<u128>,
+    is_started: bool,
+}
+
+impl PeekingReader {
+    fn new(reader: LayerReader) -> PeekingReader {
+        PeekingReader { reader, current: None, is_started: false }
+    }
+
+    /// Checks whether the layer holds the state, which has to be at least the state checked before.
+    fn contains(&mut self, packed: u128) -> io::Result<bool> {
+        if !self.is_started {
+            self.current = self.reader.next()?;
+            self.is_started = true;
+        }
+        while let Some(current) = self.current {
+            if current >= packed {
+                return Ok(current == packed);
+            }
+            self.current = self.reader.next()?;
+        }
+        Ok(false)
+    }
+}
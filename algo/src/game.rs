@@ -0,0 +1,200 @@
+use std::path::Path;
+use std::{fmt, fs, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{replay_moves, Board, Direction, Goal, GoalError, Solve, SolveError, TOTAL_PUZZLE_SIZE};
+
+/// A puzzle played by hand, one move at a time, with the moves made so far so they can be
+/// undone and the undone moves so they can be redone until another move is made.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Direction, Game, Goal};
+///
+/// let mut game: Game = Game::new(Goal::default(), [0, 1, 2, 3, 4, 5, 6, 8, 7]);
+///
+/// assert!(!game.play(Direction::Down));
+/// assert!(game.play(Direction::Right));
+/// assert!(game.is_solved());
+/// assert_eq!(game.undo(), Some(Direction::Right));
+/// assert_eq!(game.redo(), Some(Direction::Right));
+/// assert_eq!(game.moves().len(), 1);
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Game<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    start: [i8; N],
+    puzzle_state: [i8; N],
+    moves: Vec<Direction>,
+    /// The undone moves, the last one is redone first.
+    undone: Vec<Direction>,
+}
+
+impl<const N: usize> Game<N> {
+    pub fn new(goal: Goal<N>, start: [i8; N]) -> Game<N> {
+        Game {
+            goal,
+            start,
+            puzzle_state: start,
+            moves: Vec::new(),
+            undone: Vec::new(),
+        }
+    }
+
+    pub fn goal(&self) -> &Goal<N> {
+        &self.goal
+    }
+
+    pub fn start(&self) -> &[i8; N] {
+        &self.start
+    }
+
+    pub fn puzzle_state(&self) -> &[i8; N] {
+        &self.puzzle_state
+    }
+
+    /// Gets the moves from the start to the current puzzle state.
+    pub fn moves(&self) -> &[Direction] {
+        &self.moves
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.goal.is_reached(&self.puzzle_state)
+    }
+
+    /// Moves the movable piece in the direction, which forgets the undone moves.
+    /// Returns `false` and leaves the game as it was when the move isn't possible.
+    pub fn play(&mut self, direction: Direction) -> bool {
+        if !self.make_move(direction) {
+            return false;
+        }
+        self.moves.push(direction);
+        self.undone.clear();
+        true
+    }
+
+    /// Slides the tile next to the movable piece in the direction, the way a player pushes a tile.
+    pub fn slide(&mut self, direction: Direction) -> bool {
+        self.play(direction.opposite())
+    }
+
+    /// Takes back the last move and gets it, or `None` at the start.
+    pub fn undo(&mut self) -> Option<Direction> {
+        let direction: Direction = self.moves.pop()?;
+        self.make_move(direction.opposite());
+        self.undone.push(direction);
+        Some(direction)
+    }
+
+    /// Makes the last undone move again and gets it, or `None` when there is nothing to redo.
+    pub fn redo(&mut self) -> Option<Direction> {
+        let direction: Direction = self.undone.pop()?;
+        self.make_move(direction);
+        self.moves.push(direction);
+        Some(direction)
+    }
+
+    /// Asks the solver for the first move of a shortest way from the current puzzle state,
+    /// or `None` when the game is solved. The solver has to work towards the goal of the game.
+    pub fn hint(&self, solver: &mut dyn Solve<N>) -> Result<Option<Direction>, SolveError> {
+        if self.is_solved() {
+            return Ok(None);
+        }
+        let solved_state = solver.solve(&mut self.puzzle_state.clone())?;
+        Ok(solved_state.moves.first().copied())
+    }
+
+    fn make_move(&mut self, direction: Direction) -> bool {
+        match replay_moves(self.goal.board(), &self.puzzle_state, &[direction]) {
+            Ok(puzzle_state) => {
+                self.puzzle_state = puzzle_state;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Gets the game as it is written to a file.
+    pub fn saved(&self) -> SavedGame<N> {
+        SavedGame {
+            board: *self.goal.board(),
+            goal: *self.goal.puzzle_state(),
+            start: self.start,
+            moves: self.moves.clone(),
+            undone: self.undone.clone(),
+        }
+    }
+
+    /// Continues a saved game, replaying its moves and checking its undone moves can be redone.
+    pub fn from_saved(saved_game: &SavedGame<N>) -> Result<Game<N>, SavedGameError> {
+        let goal: Goal<N> = Goal::new(saved_game.board, saved_game.goal).map_err(SavedGameError::Goal)?;
+        let mut game: Game<N> = Game::new(goal, saved_game.start);
+        let redone_moves = saved_game.undone.iter().rev();
+        for (index, direction) in saved_game.moves.iter().chain(redone_moves).enumerate() {
+            if !game.play(*direction) {
+                return Err(SavedGameError::IllegalMove(index));
+            }
+        }
+        for _ in &saved_game.undone {
+            game.undo();
+        }
+        Ok(game)
+    }
+}
+
+/// A game in progress as it is written to a file, as JSON following `schema`.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct SavedGame<const N: usize = TOTAL_PUZZLE_SIZE> {
+    pub board: Board,
+    #[serde(with = "crate::schema::puzzle_state")]
+    pub goal: [i8; N],
+    #[serde(with = "crate::schema::puzzle_state")]
+    pub start: [i8; N],
+    #[serde(with = "crate::schema::moves")]
+    pub moves: Vec<Direction>,
+    /// The undone moves, the last one is redone first.
+    #[serde(with = "crate::schema::moves")]
+    pub undone: Vec<Direction>,
+}
+
+/// Reasons a saved game can't be read, written or continued.
+#[derive(Debug)]
+pub enum SavedGameError {
+    Io(io::Error),
+    Invalid(serde_json::Error),
+    Goal(GoalError),
+    /// The move at this index of the moves followed by the undone moves can't be made.
+    IllegalMove(usize),
+}
+
+impl fmt::Display for SavedGameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SavedGameError::Io(error) => write!(f, "{}", error),
+            SavedGameError::Invalid(error) => write!(f, "not a valid saved game, {}", error),
+            SavedGameError::Goal(error) => write!(f, "the goal of the saved game is invalid, {}", error),
+            SavedGameError::IllegalMove(index) => write!(f, "move {} of the saved game can't be made", index),
+        }
+    }
+}
+
+impl std::error::Error for SavedGameError {}
+
+impl<const N: usize> SavedGame<N> {
+    pub fn read(path: &Path) -> Result<SavedGame<N>, SavedGameError> {
+        let text: String = fs::read_to_string(path).map_err(SavedGameError::Io)?;
+        serde_json::from_str(&text).map_err(SavedGameError::Invalid)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), SavedGameError> {
+        let text: String = serde_json::to_string(self).map_err(SavedGameError::Invalid)?;
+        fs::write(path, text).map_err(SavedGameError::Io)
+    }
+}
@@ -0,0 +1,395 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use crate::{movable_piece, Board, Direction, TOTAL_PUZZLE_SIZE};
+
+/// Marks a position of a partial goal where any tile may end up.
+pub const WILDCARD: i8 = -1;
+
+/// The puzzle state a solver works towards on its board, together with a precomputed table
+/// holding the goal position of every tile so heuristics don't have to search for it.
+///
+/// A goal can be partial, in which case only the tiles that have a position in the
+/// table have to be in place and every other position is a `WILDCARD`.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Goal<const N: usize = TOTAL_PUZZLE_SIZE> {
+    board: Board,
+    puzzle_state: [i8; N],
+    positions: [Option<usize>; N],
+}
+
+/// Reasons a puzzle state can't be used as a goal.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum GoalError {
+    /// The board has another number of positions than the puzzle state has tiles.
+    BoardMismatch(Board, usize),
+    /// The tile is outside of `0..=movable_piece(N)`.
+    InvalidTile(i8),
+    /// The tile appears more than once.
+    DuplicateTile(i8),
+    /// The tile is missing while the goal has to place every tile.
+    MissingTile(i8),
+    /// The movable piece is on a blocked position, where it can't move from.
+    BlockedMovablePiece(usize),
+}
+
+impl fmt::Display for GoalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoalError::BoardMismatch(board, len) => write!(f, "a {} board doesn't fit {} tiles", board, len),
+            GoalError::InvalidTile(tile) => write!(f, "tile {} is not part of the puzzle", tile),
+            GoalError::DuplicateTile(tile) => write!(f, "tile {} appears more than once", tile),
+            GoalError::MissingTile(tile) => write!(f, "tile {} is missing", tile),
+            GoalError::BlockedMovablePiece(position) => write!(f, "the movable piece is on blocked position {}", position),
+        }
+    }
+}
+
+impl std::error::Error for GoalError {}
+
+impl<const N: usize> Goal<N> {
+    /// The most puzzle states `is_reachable_from` searches on a region where the tiles can't pass each other.
+    pub const MAX_SEARCHED_STATES: usize = 100_000;
+
+    /// Creates a goal from any permutation of the tiles, e.g. another puzzle state to solve towards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algo::{Board, Goal, MOVABLE_PIECE};
+    ///
+    /// let goal = Goal::new(Board::square(3), [0, 1, 2, 3, 4, 5, 6, 8, 7]).unwrap();
+    ///
+    /// assert_eq!(goal.position_of(MOVABLE_PIECE), Some(7));
+    /// ```
+    pub fn new(board: Board, puzzle_state: [i8; N]) -> Result<Goal<N>, GoalError> {
+        let goal: Goal<N> = Goal::partial(board, puzzle_state)?;
+        match goal.positions.iter().position(|position| position.is_none()) {
+            Some(tile) => Err(GoalError::MissingTile(tile as i8)),
+            None => Ok(goal),
+        }
+    }
+
+    /// Creates a goal where only the tiles that are placed have to reach their position,
+    /// every position holding a `WILDCARD` may end up with any of the remaining tiles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algo::{Board, Goal, WILDCARD};
+    ///
+    /// let goal = Goal::partial(Board::square(3), [0, 1, 2, WILDCARD, WILDCARD, WILDCARD, WILDCARD, WILDCARD, WILDCARD]).unwrap();
+    ///
+    /// assert!(goal.is_reached(&[0, 1, 2, 8, 7, 6, 5, 4, 3]));
+    /// ```
+    pub fn partial(board: Board, puzzle_state: [i8; N]) -> Result<Goal<N>, GoalError> {
+        if board.len() != N {
+            return Err(GoalError::BoardMismatch(board, N));
+        }
+        let mut positions: [Option<usize>; N] = [None; N];
+        for (position, tile) in puzzle_state.iter().enumerate() {
+            if *tile == WILDCARD {
+                continue;
+            }
+            if !(0..=movable_piece(N)).contains(tile) {
+                return Err(GoalError::InvalidTile(*tile));
+            }
+            if positions[*tile as usize].replace(position).is_some() {
+                return Err(GoalError::DuplicateTile(*tile));
+            }
+        }
+        if let Some(position) = positions[movable_piece(N) as usize].filter(|position| board.is_blocked(*position)) {
+            return Err(GoalError::BlockedMovablePiece(position));
+        }
+        Ok(Goal {
+            board,
+            puzzle_state,
+            positions,
+        })
+    }
+
+    /// The tiles in reading order with the movable piece in the bottom-right corner.
+    ///
+    /// # Panics
+    ///
+    /// This and the other layouts panic when the board doesn't have `N` positions,
+    /// or when the movable piece would end on a blocked position.
+    pub fn blank_last(board: Board) -> Goal<N> {
+        Goal::from_order(board, &reading_order(&board))
+    }
+
+    /// The tiles in reading order with the movable piece in the top-left corner.
+    pub fn blank_first(board: Board) -> Goal<N> {
+        let mut order: Vec<usize> = reading_order(&board);
+        order.rotate_left(1);
+        Goal::from_order(board, &order)
+    }
+
+    /// The tiles running clockwise from the top-left corner towards the centre,
+    /// where the movable piece ends up.
+    pub fn spiral(board: Board) -> Goal<N> {
+        Goal::from_order(board, &spiral_order(&board))
+    }
+
+    /// The tiles running left to right on even rows and right to left on odd rows,
+    /// with the movable piece at the end of the last row.
+    pub fn snake(board: Board) -> Goal<N> {
+        Goal::from_order(board, &snake_order(&board))
+    }
+
+    /// Places tile `0` at `order[0]`, tile `1` at `order[1]` and so on,
+    /// the movable piece goes to the last position of the order.
+    fn from_order(board: Board, order: &[usize]) -> Goal<N> {
+        let mut puzzle_state: [i8; N] = [0; N];
+        for (tile, position) in order.iter().enumerate() {
+            puzzle_state[*position] = tile as i8;
+        }
+        Goal::new(board, puzzle_state).expect("an order places every tile exactly once")
+    }
+
+    /// Keeps only the tiles whose goal position matches the predicate, every other position becomes a `WILDCARD`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algo::Goal;
+    ///
+    /// // solve only the top row and left column
+    /// let goal: Goal = Goal::default();
+    /// let board = *goal.board();
+    /// let goal = goal.only_positions(|position| board.row(position) == 0 || board.column(position) == 0);
+    ///
+    /// assert!(goal.is_partial());
+    /// ```
+    pub fn only_positions<P>(&self, predicate: P) -> Goal<N>
+    where
+        P: Fn(usize) -> bool,
+    {
+        let mut puzzle_state: [i8; N] = self.puzzle_state;
+        for (position, tile) in puzzle_state.iter_mut().enumerate() {
+            if !predicate(position) {
+                *tile = WILDCARD;
+            }
+        }
+        Goal::partial(self.board, puzzle_state).expect("removing tiles from a goal keeps it valid")
+    }
+
+    /// Keeps only the given tiles, every other position becomes a `WILDCARD`.
+    pub fn only_tiles(&self, tiles: &[i8]) -> Goal<N> {
+        self.only_positions(|position| tiles.contains(&self.puzzle_state[position]))
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn puzzle_state(&self) -> &[i8; N] {
+        &self.puzzle_state
+    }
+
+    /// Gets the index the given tile has in the goal state, or `None` when the tile may end up anywhere.
+    pub fn position_of(&self, tile: i8) -> Option<usize> {
+        self.positions[tile as usize]
+    }
+
+    pub fn is_partial(&self) -> bool {
+        self.puzzle_state.contains(&WILDCARD)
+    }
+
+    /// Checks whether every tile the goal constrains is in its place.
+    pub fn is_reached(&self, puzzle_state: &[i8; N]) -> bool {
+        self.puzzle_state
+            .iter()
+            .zip(puzzle_state.iter())
+            .all(|(goal_tile, tile)| *goal_tile == WILDCARD || goal_tile == tile)
+    }
+
+    /// Gets a puzzle state that reaches the goal, for a partial goal the free tiles
+    /// fill the wildcards in increasing order.
+    pub fn completed_state(&self) -> [i8; N] {
+        let mut completed_state: [i8; N] = self.puzzle_state;
+        let mut free_tiles = self.free_tiles().into_iter();
+        for tile in completed_state.iter_mut().filter(|tile| **tile == WILDCARD) {
+            *tile = free_tiles.next().expect("there is a free tile for every wildcard");
+        }
+        completed_state
+    }
+
+    /// Gets the tiles the goal doesn't place.
+    fn free_tiles(&self) -> Vec<i8> {
+        (0..=movable_piece(N)).filter(|tile| self.position_of(*tile).is_none()).collect::<Vec<_>>()
+    }
+
+    /// Checks whether the goal can be reached from the given puzzle state.
+    ///
+    /// Where the tiles can't pass each other, as on a region of the board that is a single cycle or
+    /// falls apart without one of its positions, the puzzle states the movable piece can get to are
+    /// searched, up to `MAX_SEARCHED_STATES` of them. Larger searches fall back to the parity check,
+    /// which can accept puzzle states of those regions that can't reach the goal.
+    ///
+    /// A partial goal is reachable when any of the full goals it allows is. Swapping two
+    /// unconstrained tiles flips the parity, so only goals leaving at most one tile next to
+    /// the movable piece unconstrained need more than one of those full goals checked.
+    pub fn is_reachable_from(&self, puzzle_state: &[i8; N]) -> bool {
+        let movable_piece_location: usize = puzzle_state.iter().position(|tile| *tile == movable_piece(N)).unwrap();
+        if !self.board.lets_tiles_pass(movable_piece_location) {
+            if let Some(is_reachable) = self.search_from(puzzle_state) {
+                return is_reachable;
+            }
+        }
+
+        // the tiles the movable piece can't get to never move, so the goal has to leave them where they are
+        let steps: Vec<Option<usize>> = self.board.steps_from(movable_piece_location);
+        let is_stuck = |position: usize| {
+            let tile: i8 = puzzle_state[position];
+            (self.puzzle_state[position] != WILDCARD && self.puzzle_state[position] != tile) || self.position_of(tile).is_some_and(|goal_position| goal_position != position)
+        };
+        if (0..N).any(|position| steps[position].is_none() && is_stuck(position)) {
+            return false;
+        }
+
+        let free_tiles: Vec<i8> = self.free_tiles();
+        let free_positions: Vec<usize> = (0..N).filter(|position| self.puzzle_state[*position] == WILDCARD).collect::<Vec<_>>();
+
+        let mut completed_state: [i8; N] = self.completed_state();
+        let completed: Goal<N> = Goal::new(self.board, completed_state).unwrap();
+        if completed.is_full_goal_reachable_from(puzzle_state) {
+            return true;
+        }
+
+        let free_numbered_tiles: usize = free_tiles.iter().filter(|tile| **tile != movable_piece(N)).count();
+        if free_numbered_tiles >= 2 {
+            return true;
+        }
+        if free_tiles.len() == 2 {
+            completed_state.swap(free_positions[0], free_positions[1]);
+            return Goal::new(self.board, completed_state).unwrap().is_full_goal_reachable_from(puzzle_state);
+        }
+        false
+    }
+
+    /// Gets the most puzzle states a search towards the goal can visit: the arrangements of the
+    /// tiles on the positions the movable piece gets to, half of them when every cycle has an even length.
+    /// No shortest solution takes as many moves, so solvers that get that far give up.
+    pub fn max_reachable_states(&self) -> u64 {
+        let goal_position: usize = self.completed_state().iter().position(|tile| *tile == movable_piece(N)).unwrap();
+        let positions: usize = self.board.steps_from(goal_position).iter().filter(|steps| steps.is_some()).count();
+        let arrangements: u64 = (1..=positions as u64).fold(1u64, |arrangements, factor| arrangements.saturating_mul(factor));
+        if positions >= 3 && self.board.has_even_cycles(goal_position) { arrangements / 2 } else { arrangements }
+    }
+
+    /// Searches the puzzle states the movable piece can get to for one that reaches the goal,
+    /// or gets `None` when there are more than `MAX_SEARCHED_STATES` of them.
+    fn search_from(&self, puzzle_state: &[i8; N]) -> Option<bool> {
+        let mut visited: HashSet<[i8; N]> = HashSet::from([*puzzle_state]);
+        let mut queue: VecDeque<[i8; N]> = VecDeque::from([*puzzle_state]);
+        while let Some(puzzle_state) = queue.pop_front() {
+            if self.is_reached(&puzzle_state) {
+                return Some(true);
+            }
+            let movable_piece_location: usize = puzzle_state.iter().position(|tile| *tile == movable_piece(N)).unwrap();
+            for target in Direction::MOVES.iter().filter_map(|direction| self.board.neighbour(movable_piece_location, *direction)) {
+                let mut next_state: [i8; N] = puzzle_state;
+                next_state.swap(movable_piece_location, target);
+                if visited.insert(next_state) {
+                    if visited.len() > Self::MAX_SEARCHED_STATES {
+                        return None;
+                    }
+                    queue.push_back(next_state);
+                }
+            }
+        }
+        Some(false)
+    }
+
+    /// Every move swaps the movable piece with a neighbour, which flips the parity of the
+    /// permutation between the state and the goal and moves the movable piece one step.
+    /// When every cycle of positions has an even length, the goal is therefore reachable exactly
+    /// when the permutation parity matches the parity of the steps the movable piece still has
+    /// to take. A board that wraps around an odd number of positions has cycles of odd length,
+    /// which let the movable piece flip the parity on its own, so every permutation is reachable.
+    ///
+    /// The tiles the movable piece can't get to never move and have to be in place already.
+    /// This only tells the whole story when the tiles the movable piece gets to can pass each other,
+    /// see `Board::lets_tiles_pass`, elsewhere the parity is only a condition the puzzle state has to meet.
+    fn is_full_goal_reachable_from(&self, puzzle_state: &[i8; N]) -> bool {
+        let goal_position: usize = self.position_of(movable_piece(N)).unwrap();
+        let steps: Vec<Option<usize>> = self.board.steps_from(goal_position);
+        if (0..N).any(|position| steps[position].is_none() && puzzle_state[position] != self.puzzle_state[position]) {
+            return false;
+        }
+        if !self.board.has_even_cycles(goal_position) {
+            return true;
+        }
+
+        let mut visited: [bool; N] = [false; N];
+        let mut transpositions: usize = 0;
+        for start in 0..N {
+            let mut position: usize = start;
+            let mut cycle_length: usize = 0;
+            while !visited[position] {
+                visited[position] = true;
+                position = self.position_of(puzzle_state[position]).unwrap();
+                cycle_length += 1;
+            }
+            transpositions += cycle_length.saturating_sub(1);
+        }
+
+        let blank_position: usize = puzzle_state.iter().position(|tile| *tile == movable_piece(N)).unwrap();
+        transpositions % 2 == steps[blank_position].unwrap() % 2
+    }
+}
+
+impl<const N: usize> Default for Goal<N> {
+    /// The tiles in reading order on a square board.
+    fn default() -> Self {
+        Goal::blank_last(Board::square_of(N))
+    }
+}
+
+fn reading_order(board: &Board) -> Vec<usize> {
+    (0..board.len()).collect::<Vec<_>>()
+}
+
+fn snake_order(board: &Board) -> Vec<usize> {
+    (0..board.len())
+        .map(|i: usize| {
+            let row: usize = board.row(i);
+            let column: usize = if row.is_multiple_of(2) { board.column(i) } else { board.width() - 1 - board.column(i) };
+            row * board.width() + column
+        })
+        .collect::<Vec<_>>()
+}
+
+fn spiral_order(board: &Board) -> Vec<usize> {
+    let width: i32 = board.width() as i32;
+    let mut order: Vec<usize> = Vec::with_capacity(board.len());
+    let (mut top, mut bottom, mut left, mut right) = (0, board.height() as i32 - 1, 0, width - 1);
+    while top <= bottom && left <= right {
+        for column in left..=right {
+            order.push((top * width + column) as usize);
+        }
+        for row in (top + 1)..=bottom {
+            order.push((row * width + right) as usize);
+        }
+        if top < bottom {
+            for column in (left..right).rev() {
+                order.push((bottom * width + column) as usize);
+            }
+        }
+        if left < right {
+            for row in ((top + 1)..bottom).rev() {
+                order.push((row * width + left) as usize);
+            }
+        }
+        top += 1;
+        bottom -= 1;
+        left += 1;
+        right -= 1;
+    }
+    order
+}
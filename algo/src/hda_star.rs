@@ -0,0 +1,311 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::node::movable_piece_location;
+use crate::{Direction, Goal, Heuristic, SearchLimits, SearchStats, Solve, SolveError, SolvedState, TOTAL_PUZZLE_SIZE};
+
+/// Hash distributed A*, which runs A* on a number of threads that each own the states hashing to them.
+/// A thread expands the nodes it owns and sends every generated node to the owner of its state,
+/// so every state is only stored and expanded by one thread.
+///
+/// The first goal found only gives an upper bound on the length. The threads keep expanding
+/// the nodes whose value stays below the best length found so far and stop once none of them
+/// has such a node left and no node is on its way between threads, at which point the best
+/// length is the optimal one.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Goal, HdaStar, Heuristic, Solve};
+///
+/// let mut solver = HdaStar::with_goal(Goal::default()).with_heuristic(Heuristic::LinearConflict).with_threads(4);
+/// let solved_state = solver.solve(&mut [5, 2, 3, 1, 8, 4, 6, 0, 7]).unwrap();
+///
+/// assert_eq!(solved_state.moves.len(), 22);
+/// ```
+pub struct HdaStar<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    heuristic: Heuristic,
+    limits: SearchLimits,
+    threads: usize,
+}
+
+/// A generated node on its way to the thread that owns its state.
+struct Message<const N: usize> {
+    puzzle_state: [i8; N],
+    length: i32,
+    parent: Option<[i8; N]>,
+    direction: Direction,
+}
+
+/// How a thread reached one of its states for the shortest length so far.
+struct Entry<const N: usize> {
+    length: i32,
+    parent: Option<[i8; N]>,
+    direction: Direction,
+}
+
+/// A node in the open list of a thread, popping the lowest value first and the longest length between equal values.
+#[derive(PartialEq, Eq)]
+struct OpenNode<const N: usize> {
+    value: i32,
+    length: i32,
+    puzzle_state: [i8; N],
+}
+
+impl<const N: usize> Ord for OpenNode<N> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.value.cmp(&self.value).then(self.length.cmp(&other.length)).then(self.puzzle_state.cmp(&other.puzzle_state))
+    }
+}
+
+impl<const N: usize> PartialOrd for OpenNode<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// What the threads share.
+struct Shared<'a, const N: usize> {
+    solver: &'a HdaStar<N>,
+    senders: Vec<Sender<Message<N>>>,
+    /// The number of busy threads plus the number of sent messages that have not been taken yet.
+    /// It only drops to zero once nothing is left to do, and can't rise again after that.
+    work: AtomicI64,
+    /// The length of the shortest path to the goal found so far.
+    best_length: AtomicI32,
+    expanded: AtomicU64,
+    generated: AtomicU64,
+    is_limit_reached: AtomicBool,
+    started: Instant,
+}
+
+/// The thread that owns a state.
+fn owner<const N: usize>(puzzle_state: &[i8; N], threads: usize) -> usize {
+    let mut hasher: DefaultHasher = DefaultHasher::new();
+    puzzle_state.hash(&mut hasher);
+    (hasher.finish() % threads as u64) as usize
+}
+
+impl<const N: usize> HdaStar<N> {
+    pub fn new() -> HdaStar<N> {
+        HdaStar::with_goal(Goal::default())
+    }
+
+    /// Creates a solver with a thread for every thread the machine can run at once.
+    pub fn with_goal(goal: Goal<N>) -> HdaStar<N> {
+        HdaStar {
+            goal,
+            heuristic: Heuristic::default(),
+            limits: SearchLimits::none(),
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> HdaStar<N> {
+        self.heuristic = heuristic;
+        self
+    }
+
+    pub fn with_limits(mut self, limits: SearchLimits) -> HdaStar<N> {
+        self.limits = limits;
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics when there are no threads.
+    pub fn with_threads(mut self, threads: usize) -> HdaStar<N> {
+        assert!(threads > 0, "a search needs at least one thread");
+        self.threads = threads;
+        self
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+}
+
+impl<const N: usize> Default for HdaStar<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Shared<'_, N> {
+    /// Runs the search of one thread and hands back the states it owns.
+    fn run(&self, index: usize, receiver: Receiver<Message<N>>) -> HashMap<[i8; N], Entry<N>> {
+        let mut open_list: BinaryHeap<OpenNode<N>> = BinaryHeap::new();
+        let mut entries: HashMap<[i8; N], Entry<N>> = HashMap::new();
+
+        loop {
+            while let Ok(message) = receiver.try_recv() {
+                self.open(message, &mut open_list, &mut entries);
+                self.work.fetch_sub(1, Ordering::SeqCst);
+            }
+            if self.is_limit_reached.load(Ordering::SeqCst) {
+                return entries;
+            }
+
+            match self.pop(&mut open_list, &entries) {
+                Some(node) => self.expand(index, node, &mut open_list, &mut entries),
+                None => {
+                    self.work.fetch_sub(1, Ordering::SeqCst);
+                    loop {
+                        if self.work.load(Ordering::SeqCst) == 0 || self.is_limit_reached.load(Ordering::SeqCst) {
+                            return entries;
+                        }
+                        match receiver.recv_timeout(Duration::from_millis(1)) {
+                            Ok(message) => {
+                                // become busy before taking the message, so the work never drops to zero in between
+                                self.work.fetch_add(1, Ordering::SeqCst);
+                                self.open(message, &mut open_list, &mut entries);
+                                self.work.fetch_sub(1, Ordering::SeqCst);
+                                break;
+                            }
+                            Err(RecvTimeoutError::Timeout) => continue,
+                            Err(RecvTimeoutError::Disconnected) => return entries,
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pops the open node with the lowest value, skipping nodes that were reached again with
+    /// a shorter length and leaving nodes that can't beat the best length found so far.
+    fn pop(&self, open_list: &mut BinaryHeap<OpenNode<N>>, entries: &HashMap<[i8; N], Entry<N>>) -> Option<OpenNode<N>> {
+        while let Some(node) = open_list.peek() {
+            if node.value >= self.best_length.load(Ordering::SeqCst) {
+                return None;
+            }
+            let node: OpenNode<N> = open_list.pop().unwrap();
+            if entries[&node.puzzle_state].length == node.length {
+                return Some(node);
+            }
+        }
+        None
+    }
+
+    fn open(&self, message: Message<N>, open_list: &mut BinaryHeap<OpenNode<N>>, entries: &mut HashMap<[i8; N], Entry<N>>) {
+        if entries.get(&message.puzzle_state).is_some_and(|entry| entry.length <= message.length) {
+            return;
+        }
+        let value: i32 = message.length + self.solver.heuristic.estimate(&message.puzzle_state, &self.solver.goal);
+        entries.insert(message.puzzle_state, Entry { length: message.length, parent: message.parent, direction: message.direction });
+        open_list.push(OpenNode { value, length: message.length, puzzle_state: message.puzzle_state });
+    }
+
+    fn expand(&self, index: usize, node: OpenNode<N>, open_list: &mut BinaryHeap<OpenNode<N>>, entries: &mut HashMap<[i8; N], Entry<N>>) {
+        let goal: &Goal<N> = &self.solver.goal;
+        if goal.is_reached(&node.puzzle_state) {
+            self.best_length.fetch_min(node.length, Ordering::SeqCst);
+            return;
+        }
+        let stats: SearchStats = SearchStats {
+            expanded: self.expanded.fetch_add(1, Ordering::Relaxed),
+            ..SearchStats::default()
+        };
+        if self.solver.limits.is_reached(&stats, self.started) {
+            self.is_limit_reached.store(true, Ordering::SeqCst);
+            return;
+        }
+
+        let previous_direction: Direction = entries[&node.puzzle_state].direction;
+        let movable_piece_location: usize = movable_piece_location(&node.puzzle_state);
+        for direction in Direction::MOVES {
+            if direction == previous_direction.opposite() {
+                continue;
+            }
+            let Some(target) = goal.board().neighbour(movable_piece_location, direction) else {
+                continue;
+            };
+            self.generated.fetch_add(1, Ordering::Relaxed);
+            let mut puzzle_state: [i8; N] = node.puzzle_state;
+            puzzle_state.swap(movable_piece_location, target);
+            let message: Message<N> = Message { puzzle_state, length: node.length + 1, parent: Some(node.puzzle_state), direction };
+
+            let owner: usize = owner(&puzzle_state, self.senders.len());
+            if owner == index {
+                self.open(message, open_list, entries);
+            } else {
+                self.work.fetch_add(1, Ordering::SeqCst);
+                // the owner only stops receiving before the work is done when the limit was reached
+                self.senders[owner].send(message).ok();
+            }
+        }
+    }
+}
+
+impl<const N: usize> Solve<N> for HdaStar<N> {
+    fn solve(&mut self, puzzle_state: &mut [i8; N]) -> Result<SolvedState<N>, SolveError> {
+        if !self.goal.is_reachable_from(puzzle_state) {
+            return Err(SolveError::Unsolvable);
+        }
+
+        let (senders, receivers): (Vec<Sender<Message<N>>>, Vec<Receiver<Message<N>>>) = (0..self.threads).map(|_| mpsc::channel()).unzip();
+        let shared: Shared<N> = Shared {
+            solver: self,
+            senders,
+            // every thread starts out busy, plus the message with the starting node
+            work: AtomicI64::new(self.threads as i64 + 1),
+            best_length: AtomicI32::new(i32::MAX),
+            expanded: AtomicU64::new(0),
+            generated: AtomicU64::new(0),
+            is_limit_reached: AtomicBool::new(false),
+            started: Instant::now(),
+        };
+        let start: Message<N> = Message { puzzle_state: *puzzle_state, length: 0, parent: None, direction: Direction::None };
+        shared.senders[owner(puzzle_state, self.threads)].send(start).unwrap();
+
+        let tables: Vec<HashMap<[i8; N], Entry<N>>> = thread::scope(|scope| {
+            let handles = receivers
+                .into_iter()
+                .enumerate()
+                .map(|(index, receiver)| {
+                    let shared: &Shared<N> = &shared;
+                    scope.spawn(move || shared.run(index, receiver))
+                })
+                .collect::<Vec<_>>();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>()
+        });
+
+        let stats: SearchStats = SearchStats {
+            expanded: shared.expanded.load(Ordering::SeqCst),
+            generated: shared.generated.load(Ordering::SeqCst),
+            elapsed: shared.started.elapsed(),
+        };
+        if shared.is_limit_reached.load(Ordering::SeqCst) {
+            return Err(SolveError::LimitReached(stats));
+        }
+
+        // Follow the parents back from the goal, a parent always has a shorter length than its child
+        // even when it was reached again later, so this ends at the starting state.
+        let final_puzzle_state: [i8; N] = tables
+            .iter()
+            .flat_map(|table| table.iter())
+            .filter(|(state, entry)| entry.length == shared.best_length.load(Ordering::SeqCst) && self.goal.is_reached(state))
+            .map(|(state, _)| *state)
+            .next()
+            .ok_or(SolveError::Unsolvable)?;
+        let mut moves: Vec<Direction> = Vec::new();
+        let mut state: [i8; N] = final_puzzle_state;
+        while let Some(parent) = tables[owner(&state, self.threads)][&state].parent {
+            moves.push(tables[owner(&state, self.threads)][&state].direction);
+            state = parent;
+        }
+        moves.reverse();
+
+        Ok(SolvedState {
+            puzzle_state: final_puzzle_state,
+            moves,
+            stats,
+        })
+    }
+}
@@ -0,0 +1,118 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{movable_piece, Goal, Node};
+
+/// Estimates how many moves a puzzle state still needs to reach a goal. Every heuristic
+/// only counts the tiles the goal constrains and never overestimates, so solvers using
+/// them stay optimal.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Heuristic {
+    /// The sum of the distances of the tiles to their goal position.
+    #[default]
+    Manhattan,
+    /// The number of tiles that are not at their goal position.
+    MisplacedTiles,
+    /// The manhattan distance plus two moves for every tile that has to leave its goal row or
+    /// column to let another tile in the same row or column pass.
+    LinearConflict,
+}
+
+impl Heuristic {
+    pub const ALL: [Heuristic; 3] = [Heuristic::Manhattan, Heuristic::MisplacedTiles, Heuristic::LinearConflict];
+
+    pub fn estimate<const N: usize>(&self, puzzle_state: &[i8; N], goal: &Goal<N>) -> i32 {
+        match self {
+            Heuristic::Manhattan => Node::manhattan_distance(puzzle_state, goal),
+            Heuristic::MisplacedTiles => misplaced_tiles(puzzle_state, goal),
+            Heuristic::LinearConflict => Node::manhattan_distance(puzzle_state, goal) + linear_conflicts(puzzle_state, goal),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Heuristic::Manhattan => "manhattan",
+            Heuristic::MisplacedTiles => "misplaced-tiles",
+            Heuristic::LinearConflict => "linear-conflict",
+        }
+    }
+}
+
+impl fmt::Display for Heuristic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Heuristic {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Heuristic, String> {
+        Heuristic::ALL
+            .into_iter()
+            .find(|heuristic| heuristic.name() == text)
+            .ok_or_else(|| format!("unknown heuristic '{}', expected one of manhattan, misplaced-tiles or linear-conflict", text))
+    }
+}
+
+fn misplaced_tiles<const N: usize>(puzzle_state: &[i8; N], goal: &Goal<N>) -> i32 {
+    puzzle_state
+        .iter()
+        .enumerate()
+        .filter(|(position, tile)| **tile != movable_piece(N) && goal.position_of(**tile).is_some_and(|goal_position| goal_position != *position))
+        .count() as i32
+}
+
+/// Counts two moves for every tile that has to step out of a line, where a line is a row or a column
+/// holding tiles that all belong in it. Tiles whose goal positions are in increasing order can stay,
+/// so the tiles that have to step out are the ones outside of the longest increasing run.
+///
+/// Rows and columns that wrap around are circles, where tiles pass each other by going round, so they are left out.
+fn linear_conflicts<const N: usize>(puzzle_state: &[i8; N], goal: &Goal<N>) -> i32 {
+    let board = goal.board();
+    let mut conflicts: usize = 0;
+
+    let rows: usize = if board.topology().wraps_horizontally() { 0 } else { board.height() };
+    let columns: usize = if board.topology().wraps_vertically() { 0 } else { board.width() };
+    for row in 0..rows {
+        let goal_columns: Vec<usize> = (0..board.width())
+            .map(|column| puzzle_state[row * board.width() + column])
+            .filter(|tile| *tile != movable_piece(N))
+            .filter_map(|tile| goal.position_of(tile))
+            .filter(|goal_position| board.row(*goal_position) == row)
+            .map(|goal_position| board.column(goal_position))
+            .collect::<Vec<_>>();
+        conflicts += goal_columns.len() - longest_increasing_run(&goal_columns);
+    }
+    for column in 0..columns {
+        let goal_rows: Vec<usize> = (0..board.height())
+            .map(|row| puzzle_state[row * board.width() + column])
+            .filter(|tile| *tile != movable_piece(N))
+            .filter_map(|tile| goal.position_of(tile))
+            .filter(|goal_position| board.column(*goal_position) == column)
+            .map(|goal_position| board.row(goal_position))
+            .collect::<Vec<_>>();
+        conflicts += goal_rows.len() - longest_increasing_run(&goal_rows);
+    }
+
+    2 * conflicts as i32
+}
+
+/// Gets the length of the longest increasing subsequence.
+fn longest_increasing_run(values: &[usize]) -> usize {
+    let mut lengths: Vec<usize> = vec![1; values.len()];
+    for i in 0..values.len() {
+        for j in 0..i {
+            if values[j] < values[i] {
+                lengths[i] = lengths[i].max(lengths[j] + 1);
+            }
+        }
+    }
+    lengths.into_iter().max().unwrap_or(0)
+}
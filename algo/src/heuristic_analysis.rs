@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+
+use crate::{AStar, BatchRunner, Goal, Heuristic, SearchLimits, SearchStats, Solve, TOTAL_PUZZLE_SIZE};
+
+/// How a heuristic did on a single puzzle state.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub struct InstanceQuality {
+    /// The estimate of the heuristic for the puzzle state, h(start).
+    pub estimate: i32,
+    /// The number of moves of a shortest solution, unknown when every search gave up.
+    pub distance: Option<usize>,
+    /// The work of A* with the heuristic, unknown when it gave up.
+    pub stats: Option<SearchStats>,
+}
+
+impl InstanceQuality {
+    /// Gets the branching factor a uniform tree as deep as the solution needs to hold the nodes A* generated.
+    pub fn effective_branching_factor(&self) -> Option<f64> {
+        match (self.distance, self.stats) {
+            (Some(distance), Some(stats)) if distance > 0 => Some(effective_branching_factor(stats.generated, distance)),
+            _ => None,
+        }
+    }
+}
+
+/// How a heuristic did on every puzzle state of an analysis, in the order of the puzzle states.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct HeuristicQuality {
+    pub heuristic: Heuristic,
+    pub instances: Vec<InstanceQuality>,
+}
+
+impl HeuristicQuality {
+    pub fn mean_estimate(&self) -> Option<f64> {
+        mean(self.instances.iter().map(|instance| instance.estimate as f64))
+    }
+
+    pub fn mean_distance(&self) -> Option<f64> {
+        mean(self.instances.iter().filter_map(|instance| instance.distance).map(|distance| distance as f64))
+    }
+
+    /// Gets the average of h(start) divided by the optimal distance, 1 for a perfect heuristic.
+    pub fn mean_ratio(&self) -> Option<f64> {
+        mean(self.instances.iter().filter_map(|instance| match instance.distance {
+            Some(distance) if distance > 0 => Some(instance.estimate as f64 / distance as f64),
+            _ => None,
+        }))
+    }
+
+    /// Counts the puzzle states by how many moves the heuristic falls short of the optimal distance.
+    /// A heuristic that never overestimates has no negative counts.
+    pub fn shortfall_distribution(&self) -> BTreeMap<i64, usize> {
+        let mut distribution: BTreeMap<i64, usize> = BTreeMap::new();
+        for instance in &self.instances {
+            if let Some(distance) = instance.distance {
+                *distribution.entry(distance as i64 - instance.estimate as i64).or_default() += 1;
+            }
+        }
+        distribution
+    }
+
+    pub fn mean_effective_branching_factor(&self) -> Option<f64> {
+        mean(self.instances.iter().filter_map(InstanceQuality::effective_branching_factor))
+    }
+
+    pub fn mean_expanded(&self) -> Option<f64> {
+        mean(self.instances.iter().filter_map(|instance| instance.stats).map(|stats| stats.expanded as f64))
+    }
+
+    /// Gets the number of puzzle states where A* with the heuristic gave up because of its limits.
+    pub fn gave_up(&self) -> usize {
+        self.instances.iter().filter(|instance| instance.stats.is_none()).count()
+    }
+}
+
+/// Compares heuristics on a set of puzzle states: how close their estimate of every start is to
+/// the optimal distance, and how much work A* does with them.
+///
+/// Every puzzle state is solved with A* once for every heuristic. The heuristics never overestimate,
+/// so every solution is a shortest one, and the optimal distance is the length of the shortest of them.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Heuristic, HeuristicAnalysis};
+///
+/// let analysis = HeuristicAnalysis::<9>::new().with_heuristics(&[Heuristic::MisplacedTiles, Heuristic::LinearConflict]);
+/// let qualities = analysis.analyse(&[[5, 2, 3, 1, 8, 4, 6, 0, 7]]);
+///
+/// assert_eq!(qualities[1].heuristic, Heuristic::LinearConflict);
+/// assert_eq!(qualities[1].instances[0].distance, Some(22));
+/// assert!(qualities[0].mean_estimate() < qualities[1].mean_estimate());
+/// assert!(qualities[0].mean_expanded() > qualities[1].mean_expanded());
+/// ```
+pub struct HeuristicAnalysis<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    heuristics: Vec<Heuristic>,
+    limits: SearchLimits,
+    runner: BatchRunner,
+}
+
+impl<const N: usize> HeuristicAnalysis<N> {
+    pub fn new() -> HeuristicAnalysis<N> {
+        HeuristicAnalysis::with_goal(Goal::default())
+    }
+
+    pub fn with_goal(goal: Goal<N>) -> HeuristicAnalysis<N> {
+        HeuristicAnalysis {
+            goal,
+            heuristics: Heuristic::ALL.to_vec(),
+            limits: SearchLimits::none(),
+            runner: BatchRunner::default(),
+        }
+    }
+
+    /// Sets the heuristics to compare, every one of them by default.
+    pub fn with_heuristics(mut self, heuristics: &[Heuristic]) -> HeuristicAnalysis<N> {
+        self.heuristics = heuristics.to_vec();
+        self
+    }
+
+    /// Sets the limits of every search on its own.
+    pub fn with_limits(mut self, limits: SearchLimits) -> HeuristicAnalysis<N> {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_runner(mut self, runner: BatchRunner) -> HeuristicAnalysis<N> {
+        self.runner = runner;
+        self
+    }
+
+    /// Solves the puzzle states with every heuristic and gets the quality of the heuristics in their order.
+    pub fn analyse(&self, puzzle_states: &[[i8; N]]) -> Vec<HeuristicQuality> {
+        let results: Vec<Vec<InstanceQuality>> = self.runner.run(puzzle_states, |puzzle_state| self.analyse_instance(puzzle_state)).results;
+        self.heuristics
+            .iter()
+            .enumerate()
+            .map(|(index, heuristic)| HeuristicQuality {
+                heuristic: *heuristic,
+                instances: results.iter().map(|instance| instance[index]).collect::<Vec<_>>(),
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn analyse_instance(&self, puzzle_state: &[i8; N]) -> Vec<InstanceQuality> {
+        let solutions: Vec<Option<(usize, SearchStats)>> = self
+            .heuristics
+            .iter()
+            .map(|heuristic| {
+                let mut solver: AStar<N> = AStar::with_goal(self.goal.clone()).with_heuristic(*heuristic).with_limits(self.limits.clone());
+                solver.solve(&mut puzzle_state.clone()).ok().map(|solved_state| (solved_state.moves.len(), solved_state.stats))
+            })
+            .collect::<Vec<_>>();
+        let distance: Option<usize> = solutions.iter().flatten().map(|(length, _)| *length).min();
+
+        self.heuristics
+            .iter()
+            .zip(solutions)
+            .map(|(heuristic, solution)| InstanceQuality {
+                estimate: heuristic.estimate(puzzle_state, &self.goal),
+                distance,
+                stats: solution.map(|(_, stats)| stats),
+            })
+            .collect::<Vec<_>>()
+    }
+}
+
+impl<const N: usize> Default for HeuristicAnalysis<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gets the branching factor b for which a uniform tree of the given depth holds the generated
+/// nodes plus its root, that is 1 + b + b² + ... + b^depth = generated + 1.
+///
+/// # Examples
+///
+/// ```
+/// // a root with 2 children that have 2 children each
+/// assert!((algo::effective_branching_factor(6, 2) - 2.0).abs() < 1e-9);
+/// ```
+///
+/// # Panics
+///
+/// Panics when the depth is 0, as a tree without levels has no branching factor.
+pub fn effective_branching_factor(generated: u64, depth: usize) -> f64 {
+    assert!(depth > 0, "the effective branching factor needs a depth of at least one");
+    let nodes: f64 = generated as f64 + 1.0;
+    let tree_size = |branching_factor: f64| (0..=depth as i32).map(|level| branching_factor.powi(level)).sum::<f64>();
+
+    let (mut low, mut high) = (0.0, nodes.max(1.0));
+    for _ in 0..100 {
+        let middle: f64 = (low + high) / 2.0;
+        if tree_size(middle) < nodes {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
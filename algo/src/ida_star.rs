@@ -0,0 +1,299 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::node::movable_piece_location;
+use crate::{tile_label, Checkpoint, CheckpointError, Direction, Goal, Heuristic, PatternDatabase, SearchLimits, SearchStats, Solve, SolveError, SolvedState, TOTAL_PUZZLE_SIZE};
+
+/// Iterative deepening A*, a depth-first search that only keeps the current path in memory.
+/// Every iteration searches the paths whose value stays within a bound, starting at the value of the
+/// puzzle state and raising it to the smallest value that went over it, until the goal is found.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Goal, Heuristic, IdaStar, Solve};
+///
+/// let mut solver = IdaStar::with_goal(Goal::default()).with_heuristic(Heuristic::LinearConflict);
+/// let solved_state = solver.solve(&mut [5, 2, 3, 1, 8, 4, 6, 0, 7]).unwrap();
+///
+/// assert_eq!(solved_state.moves.len(), 22);
+/// ```
+///
+/// A pattern database raises the estimate where it beats the heuristic, so fewer nodes are expanded.
+///
+/// ```
+/// use std::sync::Arc;
+/// use algo::{Goal, IdaStar, PatternDatabase, Solve};
+///
+/// let pattern_database = PatternDatabase::new(&Goal::default(), &[0, 1, 2]).unwrap();
+/// let mut solver: IdaStar = IdaStar::new().with_pattern_database(Arc::new(pattern_database));
+///
+/// assert_eq!(solver.solve(&mut [5, 2, 3, 1, 8, 4, 6, 0, 7]).unwrap().moves.len(), 22);
+/// ```
+///
+/// A long search can write checkpoints and be resumed from the last one after a restart.
+///
+/// ```no_run
+/// use std::path::Path;
+/// use std::time::Duration;
+/// use algo::{Checkpoint, IdaStar, Solve};
+///
+/// let path: &Path = Path::new("search.checkpoint");
+/// let mut solver: IdaStar = IdaStar::new().with_checkpoints(path, Duration::from_secs(60));
+/// if path.exists() {
+///     solver = solver.with_resume(Checkpoint::read(path).unwrap());
+/// }
+/// let solved_state = solver.solve(&mut [5, 2, 3, 1, 8, 4, 6, 0, 7]).unwrap();
+/// ```
+pub struct IdaStar<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    heuristic: Heuristic,
+    pattern_database: Option<Arc<PatternDatabase<N>>>,
+    limits: SearchLimits,
+    checkpoints: Option<(PathBuf, Duration)>,
+    resume: Option<Checkpoint<N>>,
+    checkpoint_error: Option<CheckpointError>,
+}
+
+/// The state of one depth-first iteration.
+struct Search<'a, const N: usize> {
+    solver: &'a IdaStar<N>,
+    start: [i8; N],
+    puzzle_state: [i8; N],
+    moves: Vec<Direction>,
+    stats: SearchStats,
+    started: Instant,
+    /// The time spent before the search was resumed.
+    elapsed_before: Duration,
+    bound: i32,
+    /// The smallest value that went over the bound so far in the iteration.
+    next_bound: Option<i32>,
+    /// The path to the node a resumed search continues from, the nodes on it were expanded before.
+    resume_path: Vec<Direction>,
+    last_checkpoint: Instant,
+    checkpoint_error: Option<CheckpointError>,
+}
+
+impl<const N: usize> IdaStar<N> {
+    pub fn new() -> IdaStar<N> {
+        IdaStar::with_goal(Goal::default())
+    }
+
+    pub fn with_goal(goal: Goal<N>) -> IdaStar<N> {
+        IdaStar {
+            goal,
+            heuristic: Heuristic::default(),
+            pattern_database: None,
+            limits: SearchLimits::none(),
+            checkpoints: None,
+            resume: None,
+            checkpoint_error: None,
+        }
+    }
+
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> IdaStar<N> {
+        self.heuristic = heuristic;
+        self
+    }
+
+    /// Estimates with the higher of the heuristic and the pattern database, which has to be built for the goal of the solver.
+    /// The database is shared, so the solvers of a batch can look up the same one.
+    pub fn with_pattern_database(mut self, pattern_database: Arc<PatternDatabase<N>>) -> IdaStar<N> {
+        self.pattern_database = Some(pattern_database);
+        self
+    }
+
+    pub fn with_limits(mut self, limits: SearchLimits) -> IdaStar<N> {
+        self.limits = limits;
+        self
+    }
+
+    /// Writes a checkpoint to the file every time the interval passes, and when a limit stops the search.
+    pub fn with_checkpoints(mut self, path: impl Into<PathBuf>, interval: Duration) -> IdaStar<N> {
+        self.checkpoints = Some((path.into(), interval));
+        self
+    }
+
+    /// Continues the search of the checkpoint in the next solve, which has to be given the same puzzle state and a
+    /// solver with the same goal, heuristic and pattern, or it fails with `SolveError::CheckpointMismatch`.
+    /// The solved state is the same as the one of a search that was never stopped, only the elapsed time can differ.
+    pub fn with_resume(mut self, checkpoint: Checkpoint<N>) -> IdaStar<N> {
+        self.resume = Some(checkpoint);
+        self
+    }
+
+    /// Tells whether a solve of the puzzle state can resume from the checkpoint.
+    pub fn can_resume(&self, checkpoint: &Checkpoint<N>, puzzle_state: &[i8; N]) -> bool {
+        checkpoint.puzzle_state == *puzzle_state
+            && checkpoint.board == *self.goal.board()
+            && checkpoint.goal == *self.goal.puzzle_state()
+            && checkpoint.heuristic == self.heuristic
+            && checkpoint.pattern == self.pattern()
+    }
+
+    /// Gets the error of the last checkpoint that couldn't be written, a search keeps going when it can't
+    /// write a checkpoint.
+    pub fn checkpoint_error(&self) -> Option<&CheckpointError> {
+        self.checkpoint_error.as_ref()
+    }
+}
+
+impl<const N: usize> IdaStar<N> {
+    fn estimate(&self, puzzle_state: &[i8; N]) -> i32 {
+        let estimate: i32 = self.heuristic.estimate(puzzle_state, &self.goal);
+        match &self.pattern_database {
+            Some(pattern_database) => estimate.max(pattern_database.estimate(puzzle_state)),
+            None => estimate,
+        }
+    }
+
+    /// Gets the labels of the pattern tiles as written in checkpoints.
+    fn pattern(&self) -> Vec<usize> {
+        self.pattern_database.as_ref().map_or(Vec::new(), |pattern_database| pattern_database.pattern().iter().map(|tile| tile_label::<N>(*tile)).collect::<Vec<_>>())
+    }
+}
+
+impl<const N: usize> Default for IdaStar<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Search<'_, N> {
+    /// Searches the node at the end of the moves, telling whether it leads to the goal within the bound.
+    fn search(&mut self, movable_piece_location: usize, length: i32) -> Result<bool, SolveError> {
+        let goal: &Goal<N> = &self.solver.goal;
+        let depth: usize = self.moves.len();
+        if depth >= self.resume_path.len() {
+            let value: i32 = length + self.solver.estimate(&self.puzzle_state);
+            if value > self.bound {
+                self.next_bound = Some(self.next_bound.map_or(value, |bound| bound.min(value)));
+                return Ok(false);
+            }
+            if goal.is_reached(&self.puzzle_state) {
+                return Ok(true);
+            }
+            if self.solver.limits.is_reached(&self.stats, self.started) {
+                self.stats.elapsed = self.elapsed();
+                self.write_checkpoint();
+                return Err(SolveError::LimitReached(self.stats));
+            }
+            if self.solver.checkpoints.as_ref().is_some_and(|(_, interval)| self.last_checkpoint.elapsed() >= *interval) {
+                self.write_checkpoint();
+            }
+            self.stats.expanded += 1;
+        }
+
+        for (index, direction) in Direction::MOVES.into_iter().enumerate() {
+            // a resumed search skips the successors it searched before and goes down the path again
+            let resume_index: Option<usize> = self.resume_path.get(depth).map(|resume_direction| Direction::MOVES.iter().position(|move_direction| move_direction == resume_direction).unwrap());
+            if resume_index.is_some_and(|resume_index| index < resume_index) {
+                continue;
+            }
+            if self.moves.last() == Some(&direction.opposite()) {
+                continue;
+            }
+            let Some(target) = goal.board().neighbour(movable_piece_location, direction) else {
+                continue;
+            };
+            if resume_index.is_none() {
+                self.stats.generated += 1;
+            }
+            self.puzzle_state.swap(movable_piece_location, target);
+            self.moves.push(direction);
+
+            let is_found: bool = self.search(target, length + 1)?;
+            self.resume_path.truncate(depth);
+            if is_found {
+                return Ok(true);
+            }
+
+            self.moves.pop();
+            self.puzzle_state.swap(movable_piece_location, target);
+        }
+
+        Ok(false)
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.elapsed_before + self.started.elapsed()
+    }
+
+    /// Writes where the search is, just before it expands the node at the end of the moves.
+    fn write_checkpoint(&mut self) {
+        let Some((path, _)) = &self.solver.checkpoints else {
+            return;
+        };
+        let checkpoint: Checkpoint<N> = Checkpoint {
+            puzzle_state: self.start,
+            board: *self.solver.goal.board(),
+            goal: *self.solver.goal.puzzle_state(),
+            heuristic: self.solver.heuristic,
+            pattern: self.solver.pattern(),
+            bound: self.bound,
+            next_bound: self.next_bound,
+            path: self.moves.clone(),
+            stats: SearchStats { elapsed: self.elapsed(), ..self.stats },
+        };
+        if let Err(error) = checkpoint.write(path) {
+            self.checkpoint_error = Some(error);
+        }
+        self.last_checkpoint = Instant::now();
+    }
+}
+
+impl<const N: usize> Solve<N> for IdaStar<N> {
+    fn solve(&mut self, puzzle_state: &mut [i8; N]) -> Result<SolvedState<N>, SolveError> {
+        if !self.goal.is_reachable_from(puzzle_state) {
+            return Err(SolveError::Unsolvable);
+        }
+
+        let resume: Option<Checkpoint<N>> = self.resume.take();
+        if resume.as_ref().is_some_and(|checkpoint| !self.can_resume(checkpoint, puzzle_state)) {
+            return Err(SolveError::CheckpointMismatch);
+        }
+        let mut search: Search<N> = Search {
+            solver: self,
+            start: *puzzle_state,
+            puzzle_state: *puzzle_state,
+            moves: Vec::new(),
+            stats: resume.as_ref().map_or(SearchStats::default(), |checkpoint| checkpoint.stats),
+            started: Instant::now(),
+            elapsed_before: resume.as_ref().map_or(Duration::ZERO, |checkpoint| checkpoint.stats.elapsed),
+            bound: resume.as_ref().map_or_else(|| self.estimate(puzzle_state), |checkpoint| checkpoint.bound),
+            next_bound: resume.as_ref().and_then(|checkpoint| checkpoint.next_bound),
+            resume_path: resume.map_or(Vec::new(), |checkpoint| checkpoint.path),
+            last_checkpoint: Instant::now(),
+            checkpoint_error: None,
+        };
+        let movable_piece_location: usize = movable_piece_location(puzzle_state);
+        let max_reachable_states: u64 = self.goal.max_reachable_states();
+
+        let result: Result<(), SolveError> = loop {
+            match search.search(movable_piece_location, 0) {
+                Ok(true) => break Ok(()),
+                Ok(false) => match search.next_bound.take() {
+                    // a shortest solution never visits a puzzle state twice
+                    Some(next_bound) if next_bound as u64 >= max_reachable_states => break Err(SolveError::Unsolvable),
+                    Some(next_bound) => search.bound = next_bound,
+                    None => break Err(SolveError::Unsolvable),
+                },
+                Err(error) => break Err(error),
+            }
+        };
+        let elapsed: Duration = search.elapsed();
+        let Search { puzzle_state: final_puzzle_state, moves, mut stats, checkpoint_error, .. } = search;
+        if checkpoint_error.is_some() {
+            self.checkpoint_error = checkpoint_error;
+        }
+        result?;
+
+        stats.elapsed = elapsed;
+        Ok(SolvedState {
+            puzzle_state: final_puzzle_state,
+            moves,
+            stats,
+        })
+    }
+}
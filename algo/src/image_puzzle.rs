@@ -0,0 +1,245 @@
+use std::path::Path;
+use std::{fmt, fs, io};
+
+use crate::{movable_piece, Goal, TOTAL_PUZZLE_SIZE};
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// A picture of 8 bit RGB pixels in reading order.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+/// Reasons an image can't be read, written or cut into tiles.
+#[derive(Debug)]
+pub enum ImageError {
+    Io(io::Error),
+    /// The bytes are neither a PNG nor a binary PPM image, the argument tells what is wrong.
+    InvalidPpm(String),
+    InvalidPng(png::DecodingError),
+    PngEncoding(png::EncodingError),
+    /// The image has fewer pixels across or down than the board has positions.
+    TooSmall(usize, usize),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::Io(error) => write!(f, "{}", error),
+            ImageError::InvalidPpm(reason) => write!(f, "not a PNG or binary PPM image, {}", reason),
+            ImageError::InvalidPng(error) => write!(f, "not a valid PNG image, {}", error),
+            ImageError::PngEncoding(error) => write!(f, "could not encode the PNG image, {}", error),
+            ImageError::TooSmall(width, height) => write!(f, "a {}x{} image is too small to cut into tiles", width, height),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+impl Image {
+    /// Creates an image of a single colour.
+    pub fn new(width: usize, height: usize, colour: [u8; 3]) -> Image {
+        Image { width, height, pixels: vec![colour; width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> [u8; 3] {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, colour: [u8; 3]) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    /// Reads a PNG or binary PPM image, told apart by their first bytes.
+    pub fn read(path: &Path) -> Result<Image, ImageError> {
+        let bytes: Vec<u8> = fs::read(path).map_err(ImageError::Io)?;
+        Image::decode(&bytes)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Image, ImageError> {
+        if bytes.starts_with(PNG_SIGNATURE) {
+            decode_png(bytes)
+        } else {
+            decode_ppm(bytes)
+        }
+    }
+
+    /// Writes the image as PNG when the file name ends in `.png` and as binary PPM otherwise.
+    pub fn write(&self, path: &Path) -> Result<(), ImageError> {
+        let is_png: bool = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+        let bytes: Vec<u8> = if is_png { self.to_png()? } else { self.to_ppm() };
+        fs::write(path, bytes).map_err(ImageError::Io)
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm: Vec<u8> = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flatten());
+        ppm
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>, ImageError> {
+        let mut png: Vec<u8> = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(ImageError::PngEncoding)?;
+        writer.write_image_data(&self.pixels.concat()).map_err(ImageError::PngEncoding)?;
+        writer.finish().map_err(ImageError::PngEncoding)?;
+        Ok(png)
+    }
+}
+
+/// Decodes any PNG into 8 bit RGB, grey is spread over the channels and alpha is dropped.
+fn decode_png(bytes: &[u8]) -> Result<Image, ImageError> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(ImageError::InvalidPng)?;
+    let mut buffer: Vec<u8> = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(ImageError::InvalidPng)?;
+    let channels: usize = info.color_type.samples();
+    let pixels: Vec<[u8; 3]> = buffer[..info.buffer_size()]
+        .chunks_exact(channels)
+        .map(|pixel| match channels {
+            1 | 2 => [pixel[0]; 3],
+            _ => [pixel[0], pixel[1], pixel[2]],
+        })
+        .collect::<Vec<_>>();
+    Ok(Image { width: info.width as usize, height: info.height as usize, pixels })
+}
+
+/// Decodes a binary PPM, the `P6` format, scaling the samples to 8 bits.
+fn decode_ppm(bytes: &[u8]) -> Result<Image, ImageError> {
+    let invalid = |reason: &str| ImageError::InvalidPpm(reason.to_string());
+    if !bytes.starts_with(b"P6") {
+        return Err(invalid("the image doesn't start with P6"));
+    }
+    // the width, height and largest sample, separated by whitespace and comments
+    let mut fields: Vec<usize> = Vec::new();
+    let mut index: usize = 2;
+    while fields.len() < 3 {
+        match bytes.get(index) {
+            Some(b'#') => index += bytes[index..].iter().position(|byte| *byte == b'\n').unwrap_or(bytes.len() - index),
+            Some(byte) if byte.is_ascii_whitespace() => index += 1,
+            Some(byte) if byte.is_ascii_digit() => {
+                let length: usize = bytes[index..].iter().take_while(|byte| byte.is_ascii_digit()).count();
+                let field: usize = std::str::from_utf8(&bytes[index..index + length]).unwrap().parse::<usize>().map_err(|_| invalid("a number in the header is too large"))?;
+                fields.push(field);
+                index += length;
+            }
+            _ => return Err(invalid("the header is incomplete")),
+        }
+    }
+    let (width, height, max_value) = (fields[0], fields[1], fields[2]);
+    if max_value == 0 || max_value > 255 {
+        return Err(invalid("only samples of 8 bits are supported"));
+    }
+    let sample_count: usize = width.checked_mul(height).and_then(|pixel_count| pixel_count.checked_mul(3)).ok_or_else(|| invalid("the image is too large"))?;
+    let samples: &[u8] = bytes.get(index + 1..).unwrap_or_default();
+    if samples.len() < sample_count {
+        return Err(invalid("the image has fewer pixels than its header tells"));
+    }
+    let scale = |sample: u8| (sample as usize * 255 / max_value).min(255) as u8;
+    let pixels: Vec<[u8; 3]> = samples[..sample_count].chunks_exact(3).map(|pixel| [scale(pixel[0]), scale(pixel[1]), scale(pixel[2])]).collect::<Vec<_>>();
+    Ok(Image { width, height, pixels })
+}
+
+/// A picture cut into the tiles of a sliding puzzle. The goal tells which part of the picture
+/// every tile shows: the part at the goal position of the tile. Drawing a puzzle state only
+/// depends on the state, so solvers and scramblers keep working on plain puzzle states.
+///
+/// Pixels that don't fill a whole tile at the right and bottom edges are left out.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Board, Goal, Image, ImagePuzzle};
+///
+/// let mut image: Image = Image::new(4, 2, [255, 255, 255]);
+/// image.set_pixel(0, 0, [255, 0, 0]);
+/// let image_puzzle: ImagePuzzle<8> = ImagePuzzle::new(image, Goal::blank_last(Board::new(4, 2))).unwrap();
+///
+/// // tile 1 moves one to the right and the blank is cleared
+/// let rendered: Image = image_puzzle.render(&[1, 0, 2, 3, 4, 5, 6, 7]);
+/// assert_eq!(rendered.pixel(1, 0), [255, 0, 0]);
+/// assert_eq!(rendered.pixel(3, 1), [0, 0, 0]);
+/// ```
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct ImagePuzzle<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    image: Image,
+    tile_width: usize,
+    tile_height: usize,
+    blank_colour: [u8; 3],
+    /// The position of every tile in the completed goal, which is where its part of the picture is.
+    home_positions: [usize; N],
+}
+
+impl<const N: usize> ImagePuzzle<N> {
+    pub fn new(image: Image, goal: Goal<N>) -> Result<ImagePuzzle<N>, ImageError> {
+        let (tile_width, tile_height) = (image.width / goal.board().width(), image.height / goal.board().height());
+        if tile_width == 0 || tile_height == 0 {
+            return Err(ImageError::TooSmall(image.width, image.height));
+        }
+        let mut home_positions: [usize; N] = [0; N];
+        for (position, tile) in goal.completed_state().iter().enumerate() {
+            home_positions[*tile as usize] = position;
+        }
+        Ok(ImagePuzzle {
+            goal,
+            image,
+            tile_width,
+            tile_height,
+            blank_colour: [0, 0, 0],
+            home_positions,
+        })
+    }
+
+    /// Sets the colour the position of the movable piece is cleared to, black by default.
+    pub fn with_blank_colour(mut self, blank_colour: [u8; 3]) -> ImagePuzzle<N> {
+        self.blank_colour = blank_colour;
+        self
+    }
+
+    pub fn goal(&self) -> &Goal<N> {
+        &self.goal
+    }
+
+    /// Gets the width and height of a tile in pixels.
+    pub fn tile_size(&self) -> (usize, usize) {
+        (self.tile_width, self.tile_height)
+    }
+
+    /// Draws the puzzle state, every tile showing its part of the picture and the movable piece cleared.
+    pub fn render(&self, puzzle_state: &[i8; N]) -> Image {
+        let board = self.goal.board();
+        let mut rendered: Image = Image::new(board.width() * self.tile_width, board.height() * self.tile_height, self.blank_colour);
+        for (position, tile) in puzzle_state.iter().enumerate() {
+            if *tile == movable_piece(N) {
+                continue;
+            }
+            let home_position: usize = self.home_positions[*tile as usize];
+            let (source_x, source_y) = (board.column(home_position) * self.tile_width, board.row(home_position) * self.tile_height);
+            let (target_x, target_y) = (board.column(position) * self.tile_width, board.row(position) * self.tile_height);
+            for y in 0..self.tile_height {
+                for x in 0..self.tile_width {
+                    rendered.set_pixel(target_x + x, target_y + y, self.image.pixel(source_x + x, source_y + y));
+                }
+            }
+        }
+        rendered
+    }
+}
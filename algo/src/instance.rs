@@ -0,0 +1,162 @@
+use std::fmt;
+
+use crate::puzzle::parse_tiles;
+use crate::{movable_piece, Board, ParseBoardError, ParseStateError};
+
+/// A named puzzle state on its board, as read from an instance file.
+///
+/// An instance file holds any number of instances. Each starts with a header line giving the
+/// board as `WIDTHxHEIGHT`, optionally followed by a name, and then has a line for every row
+/// of tiles, numbered from `1` with `0` or `_` for the movable piece. Empty lines and lines
+/// starting with `#` are skipped.
+///
+/// ```text
+/// # two 8-puzzles
+/// 3x3 easy
+/// 1 2 3
+/// 4 5 6
+/// 7 _ 8
+///
+/// 3x3 hard
+/// 8 6 7
+/// 2 5 4
+/// 3 0 1
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Instance {
+    pub name: Option<String>,
+    pub board: Board,
+    puzzle_state: Vec<i8>,
+}
+
+/// The line of an instance file that can't be read and why.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct ParseInstanceError {
+    /// The line number, counting from `1`.
+    pub line: usize,
+    pub kind: ParseInstanceErrorKind,
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum ParseInstanceErrorKind {
+    InvalidBoard(ParseBoardError),
+    /// The row has another number of tiles than the board is wide.
+    WrongRowLength(usize, usize),
+    /// The file ends before every row of the instance is read.
+    MissingRows,
+    InvalidState(ParseStateError),
+}
+
+impl fmt::Display for ParseInstanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ParseInstanceErrorKind::InvalidBoard(error) => write!(f, "{}", error),
+            ParseInstanceErrorKind::WrongRowLength(found, expected) => write!(f, "found {} tiles in the row, expected {}", found, expected),
+            ParseInstanceErrorKind::MissingRows => write!(f, "the file ends before the last row of the instance"),
+            ParseInstanceErrorKind::InvalidState(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ParseInstanceError {}
+
+impl Instance {
+    pub fn new<const N: usize>(name: Option<String>, board: Board, puzzle_state: &[i8; N]) -> Instance {
+        assert_eq!(board.len(), N, "a {} board doesn't fit {} tiles", board, N);
+        Instance {
+            name,
+            board,
+            puzzle_state: puzzle_state.to_vec(),
+        }
+    }
+
+    /// Gets the puzzle state, or `None` when the instance doesn't have `N` tiles.
+    pub fn puzzle_state<const N: usize>(&self) -> Option<[i8; N]> {
+        self.puzzle_state.clone().try_into().ok()
+    }
+
+    /// Gets the name, or the instance number counting from `1` when it has no name.
+    pub fn name_or(&self, index: usize) -> String {
+        self.name.clone().unwrap_or_else(|| (index + 1).to_string())
+    }
+}
+
+impl fmt::Display for Instance {
+    /// Writes the instance the way `parse_instances` reads it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.board)?;
+        if let Some(name) = &self.name {
+            write!(f, " {}", name)?;
+        }
+        let len: usize = self.board.len();
+        let cell_width: usize = (len - 1).to_string().len();
+        for row in self.puzzle_state.chunks(self.board.width()) {
+            writeln!(f)?;
+            let labels = row
+                .iter()
+                .map(|tile| match *tile == movable_piece(len) {
+                    true => format!("{:>width$}", "_", width = cell_width),
+                    false => format!("{:>width$}", tile + 1, width = cell_width),
+                })
+                .collect::<Vec<_>>();
+            write!(f, "{}", labels.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads every instance of an instance file.
+///
+/// # Examples
+///
+/// ```
+/// let instances = algo::parse_instances("2x2 tiny\n1 2\n_ 3\n").unwrap();
+///
+/// assert_eq!(instances[0].name.as_deref(), Some("tiny"));
+/// assert_eq!(instances[0].puzzle_state::<4>(), Some([0, 1, 3, 2]));
+/// ```
+pub fn parse_instances(text: &str) -> Result<Vec<Instance>, ParseInstanceError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+    let mut instances: Vec<Instance> = Vec::new();
+
+    while let Some((header_line, header)) = lines.next() {
+        let (board, name) = match header.split_once(char::is_whitespace) {
+            Some((board, name)) => (board, Some(name.trim().to_string())),
+            None => (header, None),
+        };
+        let board: Board = board
+            .parse::<Board>()
+            .map_err(|error| ParseInstanceError { line: header_line, kind: ParseInstanceErrorKind::InvalidBoard(error) })?;
+
+        let mut labels: Vec<&str> = Vec::with_capacity(board.len());
+        for _ in 0..board.height() {
+            let (line, row) = lines.next().ok_or(ParseInstanceError { line: header_line, kind: ParseInstanceErrorKind::MissingRows })?;
+            let row_labels: Vec<&str> = row.split(|c: char| c.is_whitespace() || c == ',').filter(|label| !label.is_empty()).collect::<Vec<_>>();
+            if row_labels.len() != board.width() {
+                return Err(ParseInstanceError { line, kind: ParseInstanceErrorKind::WrongRowLength(row_labels.len(), board.width()) });
+            }
+            labels.extend(row_labels);
+        }
+        let puzzle_state: Vec<i8> = parse_tiles(&labels, board.len())
+            .map_err(|error| ParseInstanceError { line: header_line, kind: ParseInstanceErrorKind::InvalidState(error) })?;
+
+        instances.push(Instance { name, board, puzzle_state });
+    }
+    Ok(instances)
+}
+
+/// Writes the instances as an instance file, separated by empty lines.
+pub fn format_instances(instances: &[Instance]) -> String {
+    instances.iter().map(|instance| format!("{}\n", instance)).collect::<Vec<_>>().join("\n")
+}
@@ -0,0 +1,447 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+use std::time::Instant;
+
+use crate::{Board, Direction, ParseStateError, SearchLimits, SearchStats, SolveError, TOTAL_PUZZLE_SIZE};
+
+/// The label of a blank position.
+pub const BLANK_LABEL: i8 = 0;
+
+/// A move of a puzzle with several blanks: the blank at the position moves in the direction,
+/// swapping places with the tile there.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub struct LabelledMove {
+    pub position: usize,
+    pub direction: Direction,
+}
+
+impl fmt::Display for LabelledMove {
+    /// Writes the position of the blank followed by the letter of the direction, e.g. `4L`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.position, self.direction)
+    }
+}
+
+/// Reasons labels can't be used as a goal.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum LabelledGoalError {
+    /// The board has another number of positions than there are labels.
+    BoardMismatch(Board, usize),
+    /// The label is negative.
+    InvalidLabel(i8),
+    /// No position is blank, so nothing can move.
+    NoBlank,
+}
+
+impl fmt::Display for LabelledGoalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LabelledGoalError::BoardMismatch(board, len) => write!(f, "a {} board doesn't fit {} labels", board, len),
+            LabelledGoalError::InvalidLabel(label) => write!(f, "label {} is negative", label),
+            LabelledGoalError::NoBlank => write!(f, "the goal has no blank"),
+        }
+    }
+}
+
+impl std::error::Error for LabelledGoalError {}
+
+/// The goal of a puzzle whose positions hold labels instead of distinct tiles: any number of
+/// positions can be blank and several tiles can share a label, e.g. a puzzle of coloured tiles.
+/// Tiles with the same label can't be told apart, so any of them can end on any goal position
+/// of their label.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Board, LabelledGoal};
+///
+/// // two rows of colours with two blanks
+/// let goal: LabelledGoal<8> = LabelledGoal::new(Board::new(4, 2), [1, 1, 1, 0, 2, 2, 2, 0]).unwrap();
+///
+/// assert_eq!(goal.blanks(), 2);
+/// assert_eq!(goal.estimate(&[2, 1, 1, 0, 1, 2, 2, 0]), 2);
+/// assert!(goal.is_reachable_from(&[2, 2, 0, 1, 1, 0, 1, 2]));
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct LabelledGoal<const N: usize = TOTAL_PUZZLE_SIZE> {
+    board: Board,
+    labels: [i8; N],
+    /// The goal positions of every label, indexed by the label.
+    positions: Vec<Vec<usize>>,
+}
+
+impl<const N: usize> LabelledGoal<N> {
+    pub fn new(board: Board, labels: [i8; N]) -> Result<LabelledGoal<N>, LabelledGoalError> {
+        if board.len() != N {
+            return Err(LabelledGoalError::BoardMismatch(board, N));
+        }
+        if let Some(label) = labels.iter().find(|label| **label < 0) {
+            return Err(LabelledGoalError::InvalidLabel(*label));
+        }
+        if !labels.contains(&BLANK_LABEL) {
+            return Err(LabelledGoalError::NoBlank);
+        }
+
+        let mut positions: Vec<Vec<usize>> = vec![Vec::new(); *labels.iter().max().unwrap() as usize + 1];
+        for (position, label) in labels.iter().enumerate() {
+            positions[*label as usize].push(position);
+        }
+        Ok(LabelledGoal { board, labels, positions })
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn labels(&self) -> &[i8; N] {
+        &self.labels
+    }
+
+    /// Gets the number of blank positions.
+    pub fn blanks(&self) -> usize {
+        self.positions[BLANK_LABEL as usize].len()
+    }
+
+    pub fn is_reached(&self, labels: &[i8; N]) -> bool {
+        self.labels == *labels
+    }
+
+    /// Gets the moves of the labels, one for every blank and direction that moves a tile.
+    /// Blanks on blocked positions never move.
+    pub fn moves(&self, labels: &[i8; N]) -> Vec<LabelledMove> {
+        let mut moves: Vec<LabelledMove> = Vec::new();
+        for position in (0..N).filter(|position| labels[*position] == BLANK_LABEL && !self.board.is_blocked(*position)) {
+            for direction in Direction::MOVES {
+                if self.board.neighbour(position, direction).is_some_and(|target| labels[target] != BLANK_LABEL) {
+                    moves.push(LabelledMove { position, direction });
+                }
+            }
+        }
+        moves
+    }
+
+    /// Makes the move on the labels, which fails when the move isn't one of `moves`.
+    pub fn apply(&self, labels: &mut [i8; N], labelled_move: LabelledMove) -> Result<(), LabelledMove> {
+        let target: Option<usize> = match labels.get(labelled_move.position) {
+            Some(&BLANK_LABEL) if !self.board.is_blocked(labelled_move.position) => self.board.neighbour(labelled_move.position, labelled_move.direction),
+            _ => None,
+        };
+        match target {
+            Some(target) if labels[target] != BLANK_LABEL => {
+                labels.swap(labelled_move.position, target);
+                Ok(())
+            }
+            _ => Err(labelled_move),
+        }
+    }
+
+    /// Estimates the remaining moves as the lowest sum of distances over all ways to assign the tiles
+    /// of every label to the goal positions of that label. Every move takes a single tile a single step,
+    /// so the estimate never overestimates and changes by at most one with every move.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the labels hold another number of tiles of a label than the goal.
+    pub fn estimate(&self, labels: &[i8; N]) -> i32 {
+        let mut estimate: i32 = 0;
+        for (label, goal_positions) in self.positions.iter().enumerate().skip(1) {
+            let positions: Vec<usize> = (0..N).filter(|position| labels[*position] as usize == label).collect::<Vec<_>>();
+            estimate += match goal_positions.len() {
+                0 => 0,
+                1 => self.board.distance(positions[0], goal_positions[0]) as i32,
+                _ => {
+                    let costs: Vec<Vec<i32>> = positions
+                        .iter()
+                        .map(|position| goal_positions.iter().map(|goal_position| self.board.distance(*position, *goal_position) as i32).collect::<Vec<_>>())
+                        .collect::<Vec<_>>();
+                    minimum_assignment(&costs)
+                }
+            };
+        }
+        estimate
+    }
+
+    /// Checks whether the goal can be reached from the labels.
+    ///
+    /// The blanks split the open positions into the regions they can move around in, and each
+    /// region has to hold the same labels as in the goal. A region without a blank never changes.
+    /// A region with a single blank and no shared labels is an ordinary sliding puzzle, which can
+    /// only reach half of the arrangements unless it has a cycle of odd length. Two tiles with the
+    /// same label make both halves look the same, and a second blank lets the tiles pass each other,
+    /// so every arrangement of such a region is reachable. Like `Goal::is_reachable_from`, this
+    /// accepts more than can be reached in regions that are a single cycle or fall apart without
+    /// one of their positions.
+    pub fn is_reachable_from(&self, labels: &[i8; N]) -> bool {
+        let mut region_of: Vec<Option<usize>> = vec![None; N];
+        let mut regions: Vec<Vec<usize>> = Vec::new();
+        for start in 0..N {
+            if region_of[start].is_some() {
+                continue;
+            }
+            let region: Vec<usize> = if self.board.is_blocked(start) {
+                vec![start]
+            } else {
+                let steps: Vec<Option<usize>> = self.board.steps_from(start);
+                (0..N).filter(|position| steps[*position].is_some()).collect::<Vec<_>>()
+            };
+            for position in &region {
+                region_of[*position] = Some(regions.len());
+            }
+            regions.push(region);
+        }
+
+        regions.iter().all(|region| self.is_region_reachable(region, labels))
+    }
+
+    fn is_region_reachable(&self, region: &[usize], labels: &[i8; N]) -> bool {
+        let mut goal_labels: Vec<i8> = region.iter().map(|position| self.labels[*position]).collect::<Vec<_>>();
+        let mut state_labels: Vec<i8> = region.iter().map(|position| labels[*position]).collect::<Vec<_>>();
+        let blanks: usize = goal_labels.iter().filter(|label| **label == BLANK_LABEL).count();
+        if blanks == 0 || self.board.is_blocked(region[0]) {
+            return goal_labels == state_labels;
+        }
+        goal_labels.sort();
+        state_labels.sort();
+        if goal_labels != state_labels {
+            return false;
+        }
+        let has_shared_label: bool = goal_labels.windows(2).any(|pair| pair[0] == pair[1]);
+        if has_shared_label {
+            return true;
+        }
+
+        let goal_blank: usize = region.iter().copied().find(|position| self.labels[*position] == BLANK_LABEL).unwrap();
+        if !self.board.has_even_cycles(goal_blank) {
+            return true;
+        }
+        let goal_position = |label: i8| region.iter().copied().find(|position| self.labels[*position] == label).unwrap();
+        let mut visited: Vec<bool> = vec![false; N];
+        let mut transpositions: usize = 0;
+        for start in region {
+            let mut position: usize = *start;
+            let mut cycle_length: usize = 0;
+            while !visited[position] {
+                visited[position] = true;
+                position = goal_position(labels[position]);
+                cycle_length += 1;
+            }
+            transpositions += cycle_length.saturating_sub(1);
+        }
+        let blank: usize = region.iter().copied().find(|position| labels[*position] == BLANK_LABEL).unwrap();
+        transpositions % 2 == self.board.steps_from(goal_blank)[blank].unwrap() % 2
+    }
+}
+
+/// Gets the lowest total cost of assigning every row to a distinct column of a square matrix,
+/// with the Hungarian method.
+fn minimum_assignment(costs: &[Vec<i32>]) -> i32 {
+    let len: usize = costs.len();
+    // Rows and columns are counted from 1, column 0 holds the row that is being assigned.
+    let mut row_potentials: Vec<i32> = vec![0; len + 1];
+    let mut column_potentials: Vec<i32> = vec![0; len + 1];
+    let mut assigned_rows: Vec<usize> = vec![0; len + 1];
+    let mut previous_columns: Vec<usize> = vec![0; len + 1];
+    for row in 1..=len {
+        assigned_rows[0] = row;
+        let mut column: usize = 0;
+        let mut slack: Vec<i32> = vec![i32::MAX; len + 1];
+        let mut is_used: Vec<bool> = vec![false; len + 1];
+        loop {
+            is_used[column] = true;
+            let assigned_row: usize = assigned_rows[column];
+            let mut delta: i32 = i32::MAX;
+            let mut next_column: usize = 0;
+            for other in 1..=len {
+                if is_used[other] {
+                    continue;
+                }
+                let reduced_cost: i32 = costs[assigned_row - 1][other - 1] - row_potentials[assigned_row] - column_potentials[other];
+                if reduced_cost < slack[other] {
+                    slack[other] = reduced_cost;
+                    previous_columns[other] = column;
+                }
+                if slack[other] < delta {
+                    delta = slack[other];
+                    next_column = other;
+                }
+            }
+            for other in 0..=len {
+                if is_used[other] {
+                    row_potentials[assigned_rows[other]] += delta;
+                    column_potentials[other] -= delta;
+                } else {
+                    slack[other] -= delta;
+                }
+            }
+            column = next_column;
+            if assigned_rows[column] == 0 {
+                break;
+            }
+        }
+        while column != 0 {
+            let previous_column: usize = previous_columns[column];
+            assigned_rows[column] = assigned_rows[previous_column];
+            column = previous_column;
+        }
+    }
+    (1..=len).map(|column| costs[assigned_rows[column] - 1][column - 1]).sum()
+}
+
+/// The labels a solver ended in and the moves that lead there.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct LabelledSolution<const N: usize = TOTAL_PUZZLE_SIZE> {
+    pub labels: [i8; N],
+    pub moves: Vec<LabelledMove>,
+    pub stats: SearchStats,
+}
+
+/// A* for puzzles with labels, guided by the assignment estimate of the goal.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Board, LabelledAStar, LabelledGoal};
+///
+/// let goal: LabelledGoal<4> = LabelledGoal::new(Board::square(2), [1, 1, 0, 0]).unwrap();
+/// let solution = LabelledAStar::with_goal(goal).solve(&[0, 1, 1, 0]).unwrap();
+///
+/// assert_eq!(solution.labels, [1, 1, 0, 0]);
+/// assert_eq!(solution.moves.len(), 1);
+/// ```
+pub struct LabelledAStar<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: LabelledGoal<N>,
+    limits: SearchLimits,
+}
+
+/// An entry of the open list, ordered so the `BinaryHeap` pops the lowest value first
+/// and, between equal values, the longest path.
+#[derive(PartialEq, Eq)]
+struct OpenEntry<const N: usize> {
+    value: i32,
+    length: i32,
+    labels: [i8; N],
+}
+
+impl<const N: usize> Ord for OpenEntry<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.value.cmp(&self.value).then(self.length.cmp(&other.length))
+    }
+}
+
+impl<const N: usize> PartialOrd for OpenEntry<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> LabelledAStar<N> {
+    pub fn with_goal(goal: LabelledGoal<N>) -> LabelledAStar<N> {
+        LabelledAStar { goal, limits: SearchLimits::none() }
+    }
+
+    pub fn with_limits(mut self, limits: SearchLimits) -> LabelledAStar<N> {
+        self.limits = limits;
+        self
+    }
+
+    pub fn goal(&self) -> &LabelledGoal<N> {
+        &self.goal
+    }
+
+    /// Finds the fewest moves from the labels to the goal.
+    pub fn solve(&mut self, labels: &[i8; N]) -> Result<LabelledSolution<N>, SolveError> {
+        if !self.goal.is_reachable_from(labels) {
+            return Err(SolveError::Unsolvable);
+        }
+
+        let started: Instant = Instant::now();
+        let mut stats: SearchStats = SearchStats::default();
+        let mut open_list: BinaryHeap<OpenEntry<N>> = BinaryHeap::new();
+        // The shortest length every reached state has and the move that reached it that way.
+        let mut reached: HashMap<[i8; N], (i32, Option<LabelledMove>)> = HashMap::new();
+        reached.insert(*labels, (0, None));
+        open_list.push(OpenEntry { value: self.goal.estimate(labels), length: 0, labels: *labels });
+
+        let final_labels: [i8; N] = loop {
+            let Some(entry) = open_list.pop() else {
+                return Err(SolveError::Unsolvable);
+            };
+            if reached[&entry.labels].0 < entry.length {
+                continue;
+            }
+            if self.goal.is_reached(&entry.labels) {
+                break entry.labels;
+            }
+            if self.limits.is_reached(&stats, started) {
+                stats.elapsed = started.elapsed();
+                return Err(SolveError::LimitReached(stats));
+            }
+            stats.expanded += 1;
+
+            for labelled_move in self.goal.moves(&entry.labels) {
+                stats.generated += 1;
+                let mut next_labels: [i8; N] = entry.labels;
+                self.goal.apply(&mut next_labels, labelled_move).expect("the move was generated for these labels");
+                let length: i32 = entry.length + 1;
+                if reached.get(&next_labels).is_some_and(|(reached_length, _)| *reached_length <= length) {
+                    continue;
+                }
+                reached.insert(next_labels, (length, Some(labelled_move)));
+                open_list.push(OpenEntry { value: length + self.goal.estimate(&next_labels), length, labels: next_labels });
+            }
+        };
+
+        let mut moves: Vec<LabelledMove> = Vec::new();
+        let mut current_labels: [i8; N] = final_labels;
+        while let (_, Some(labelled_move)) = reached[&current_labels] {
+            // Undoing a move moves the blank, now at the target, back in the opposite direction.
+            let target: usize = self.goal.board.neighbour(labelled_move.position, labelled_move.direction).unwrap();
+            current_labels.swap(labelled_move.position, target);
+            moves.push(labelled_move);
+        }
+        moves.reverse();
+        stats.elapsed = started.elapsed();
+        Ok(LabelledSolution { labels: final_labels, moves, stats })
+    }
+}
+
+/// Reads labels separated by whitespace or commas, with `0` or `_` for a blank.
+/// Unlike `parse_puzzle_state`, labels can repeat and there can be any number of blanks.
+///
+/// # Examples
+///
+/// ```
+/// let labels: [i8; 6] = algo::parse_labels("1 1 _ 2 2 0").unwrap();
+///
+/// assert_eq!(labels, [1, 1, 0, 2, 2, 0]);
+/// ```
+pub fn parse_labels<const N: usize>(text: &str) -> Result<[i8; N], ParseStateError> {
+    let labels: Vec<i8> = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|label| !label.is_empty())
+        .map(|label| match label {
+            "_" => Ok(BLANK_LABEL),
+            _ => label.parse::<i8>().ok().filter(|label| *label >= 0).ok_or_else(|| ParseStateError::InvalidTile(label.to_string())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let len: usize = labels.len();
+    labels.try_into().map_err(|_| ParseStateError::WrongTileCount(len, N))
+}
+
+/// Writes the labels on a single line the way `parse_labels` reads them, with `_` for the blanks.
+pub fn format_labels<const N: usize>(labels: &[i8; N]) -> String {
+    labels
+        .iter()
+        .map(|label| if *label == BLANK_LABEL { "_".to_string() } else { label.to_string() })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,76 @@
+mod a_star;
+mod batch;
+mod bidirectional;
+mod board;
+mod checkpoint;
+mod differential;
+mod direction;
+mod distance_table;
+mod external_bfs;
+mod game;
+mod goal;
+mod hda_star;
+mod heuristic;
+mod heuristic_analysis;
+mod ida_star;
+mod image_puzzle;
+mod instance;
+mod labelled;
+mod moves;
+mod node;
+mod optimiser;
+mod pattern_database;
+mod portfolio;
+mod puzzle;
+mod render;
+pub mod schema;
+mod scrambler;
+mod sma_star;
+mod symmetry;
+mod solve;
+mod solutions;
+mod tie_breaking;
+
+pub use crate::a_star::AStar;
+pub use crate::batch::{BatchResults, BatchRunner};
+pub use crate::bidirectional::BidirectionalAStar;
+pub use crate::board::{Board, ParseBoardError, Topology};
+pub use crate::checkpoint::{Checkpoint, CheckpointError};
+pub use crate::differential::{DifferentialFailure, DifferentialReport, DifferentialTester, Discrepancy, InstanceCheck};
+pub use crate::direction::{Direction, MoveNotation, ParseMovesError};
+pub use crate::distance_table::DistanceTable;
+pub use crate::external_bfs::{BfsReport, ExternalBfs, ExternalBfsError};
+pub use crate::game::{Game, SavedGame, SavedGameError};
+pub use crate::goal::{Goal, GoalError, WILDCARD};
+pub use crate::hda_star::HdaStar;
+pub use crate::heuristic::Heuristic;
+pub use crate::heuristic_analysis::{effective_branching_factor, HeuristicAnalysis, HeuristicQuality, InstanceQuality};
+pub use crate::ida_star::IdaStar;
+pub use crate::image_puzzle::{Image, ImageError, ImagePuzzle};
+pub use crate::instance::{format_instances, parse_instances, Instance, ParseInstanceError, ParseInstanceErrorKind};
+pub use crate::labelled::{format_labels, parse_labels, LabelledAStar, LabelledGoal, LabelledGoalError, LabelledMove, LabelledSolution, BLANK_LABEL};
+pub use crate::moves::{compose_moves, invert_moves, replay_moves, replay_steps, simplify_moves, verify_solution, IllegalMove, VerifyError};
+pub use crate::node::Node;
+pub use crate::optimiser::{OptimisedMoves, SolutionOptimiser, WindowSearch};
+pub use crate::pattern_database::PatternDatabase;
+pub use crate::portfolio::Portfolio;
+pub use crate::puzzle::{
+    check_solvability, create_array_with_increasing_value, create_puzzle_state, draw_puzzle_state, format_puzzle_state, parse_puzzle_state,
+    tile_label, ParseStateError,
+};
+pub use crate::render::Renderer;
+pub use crate::scrambler::{ScrambleError, ScrambleMode, Scrambler};
+pub use crate::solutions::{Solution, Solutions};
+pub use crate::sma_star::SmaStar;
+pub use crate::solve::{CancelFlag, SearchLimits, SearchStats, Solve, SolveError, SolvedState};
+pub use crate::symmetry::Symmetry;
+pub use crate::tie_breaking::TieBreaking;
+
+pub const PUZZLE_SIZE: i8 = 3;
+pub const TOTAL_PUZZLE_SIZE: usize = 3 * 3;
+pub const MOVABLE_PIECE: i8 = movable_piece(TOTAL_PUZZLE_SIZE);
+
+/// Gets the tile that marks the movable piece in a puzzle with `size` tiles, which is the highest one.
+pub const fn movable_piece(size: usize) -> i8 {
+    (size as i8) - 1
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+
+use crate::node::movable_piece_location;
+use crate::{Board, Direction, Goal, SolvedState, TOTAL_PUZZLE_SIZE};
+
+/// Gets the moves that undo the given moves.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{invert_moves, MoveNotation};
+///
+/// let moves = MoveNotation::Blank.parse("LLU").unwrap();
+///
+/// assert_eq!(MoveNotation::Blank.format(&invert_moves(&moves)), "DRR");
+/// ```
+pub fn invert_moves(moves: &[Direction]) -> Vec<Direction> {
+    moves.iter().rev().map(|direction| direction.opposite()).collect::<Vec<_>>()
+}
+
+/// Removes every move that is directly undone by the next one, until no such pair is left.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{simplify_moves, MoveNotation};
+///
+/// let moves = MoveNotation::Blank.parse("LURDDUL").unwrap();
+///
+/// assert_eq!(MoveNotation::Blank.format(&simplify_moves(&moves)), "LURDL");
+/// ```
+pub fn simplify_moves(moves: &[Direction]) -> Vec<Direction> {
+    let mut simplified: Vec<Direction> = Vec::with_capacity(moves.len());
+    for direction in moves.iter().filter(|direction| **direction != Direction::None) {
+        if simplified.last() == Some(&direction.opposite()) {
+            simplified.pop();
+        } else {
+            simplified.push(*direction);
+        }
+    }
+    simplified
+}
+
+/// Gets the moves that first make the `first` and then the `second` moves, simplified.
+pub fn compose_moves(first: &[Direction], second: &[Direction]) -> Vec<Direction> {
+    let mut moves: Vec<Direction> = first.to_vec();
+    moves.extend_from_slice(second);
+    simplify_moves(&moves)
+}
+
+/// A move that takes the movable piece out of the puzzle.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub struct IllegalMove<const N: usize = TOTAL_PUZZLE_SIZE> {
+    /// The index of the move in the replayed moves.
+    pub index: usize,
+    pub direction: Direction,
+    /// The puzzle state right before the move.
+    pub puzzle_state: [i8; N],
+}
+
+impl<const N: usize> fmt::Display for IllegalMove<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} ({:?}) takes the movable piece out of the puzzle", self.index, self.direction)
+    }
+}
+
+impl<const N: usize> std::error::Error for IllegalMove<N> {}
+
+/// Makes the moves on the puzzle state and gets the state they end up in,
+/// or the first move that can't be made.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{replay_moves, Board, MoveNotation};
+///
+/// let start: [i8; 9] = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+/// let board = Board::square(3);
+///
+/// assert_eq!(replay_moves(&board, &start, &MoveNotation::Blank.parse("U").unwrap()).unwrap(), [0, 1, 2, 3, 4, 8, 6, 7, 5]);
+/// assert_eq!(replay_moves(&board, &start, &MoveNotation::Blank.parse("UR").unwrap()).unwrap_err().index, 1);
+/// ```
+pub fn replay_moves<const N: usize>(board: &Board, puzzle_state: &[i8; N], moves: &[Direction]) -> Result<[i8; N], IllegalMove<N>> {
+    let mut puzzle_state: [i8; N] = *puzzle_state;
+    let mut movable_piece_location: usize = movable_piece_location(&puzzle_state);
+    for (index, direction) in moves.iter().enumerate() {
+        let Some(target) = board.neighbour(movable_piece_location, *direction) else {
+            return Err(IllegalMove { index, direction: *direction, puzzle_state });
+        };
+        puzzle_state.swap(movable_piece_location, target);
+        movable_piece_location = target;
+    }
+    Ok(puzzle_state)
+}
+
+/// Gets the puzzle state before the first move and after every move, or the first move that can't be made.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{replay_steps, Board, MoveNotation};
+///
+/// let steps = replay_steps(&Board::square(2), &[0, 1, 2, 3], &MoveNotation::Blank.parse("UL").unwrap()).unwrap();
+///
+/// assert_eq!(steps, vec![[0, 1, 2, 3], [0, 3, 2, 1], [3, 0, 2, 1]]);
+/// ```
+pub fn replay_steps<const N: usize>(board: &Board, puzzle_state: &[i8; N], moves: &[Direction]) -> Result<Vec<[i8; N]>, IllegalMove<N>> {
+    let mut steps: Vec<[i8; N]> = vec![*puzzle_state];
+    for (index, direction) in moves.iter().enumerate() {
+        let step: [i8; N] = replay_moves(board, steps.last().unwrap(), &[*direction]).map_err(|illegal_move| IllegalMove { index, ..illegal_move })?;
+        steps.push(step);
+    }
+    Ok(steps)
+}
+
+/// Reasons a `SolvedState` doesn't solve the puzzle state it was created for.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum VerifyError<const N: usize = TOTAL_PUZZLE_SIZE> {
+    IllegalMove(IllegalMove<N>),
+    /// The moves end up in another state than the one the solver reported.
+    WrongFinalState([i8; N]),
+    /// The moves end up in a state that doesn't reach the goal.
+    GoalNotReached([i8; N]),
+}
+
+impl<const N: usize> fmt::Display for VerifyError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::IllegalMove(illegal_move) => write!(f, "{}", illegal_move),
+            VerifyError::WrongFinalState(puzzle_state) => write!(f, "the moves end up in {:?} instead of the reported state", puzzle_state),
+            VerifyError::GoalNotReached(puzzle_state) => write!(f, "the moves end up in {:?}, which doesn't reach the goal", puzzle_state),
+        }
+    }
+}
+
+impl<const N: usize> std::error::Error for VerifyError<N> {}
+
+/// Replays the moves of a `SolvedState` on the puzzle state it was created for, independent of the solver,
+/// and checks they end up in the reported state and reach the goal.
+pub fn verify_solution<const N: usize>(puzzle_state: &[i8; N], solved_state: &SolvedState<N>, goal: &Goal<N>) -> Result<(), VerifyError<N>> {
+    let final_puzzle_state: [i8; N] = replay_moves(goal.board(), puzzle_state, &solved_state.moves).map_err(VerifyError::IllegalMove)?;
+    if final_puzzle_state != solved_state.puzzle_state {
+        return Err(VerifyError::WrongFinalState(final_puzzle_state));
+    }
+    if !goal.is_reached(&final_puzzle_state) {
+        return Err(VerifyError::GoalNotReached(final_puzzle_state));
+    }
+    Ok(())
+}
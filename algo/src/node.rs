@@ -0,0 +1,153 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{movable_piece, Board, Direction, Goal, Heuristic, TOTAL_PUZZLE_SIZE};
+
+#[macro_export]
+macro_rules! filter_movable_nodes {
+    ($nodes:expr) => {{
+        $nodes.into_iter().filter(|n| n.is_movable).collect::<Vec<_>>()
+    }}
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Node<const N: usize = TOTAL_PUZZLE_SIZE> {
+    pub puzzle_state: [i8; N],
+    pub length: i32,
+    pub distance: i32,
+    pub value: i32,
+    pub direction: Direction,
+    pub parent_node: Option<Rc<RefCell<Node<N>>>>,
+    pub is_movable: bool,
+    pub is_ending_node: bool,
+}
+
+impl<const N: usize> Node<N> {
+    pub fn new(puzzle_state: [i8; N], goal: &Goal<N>, heuristic: &Heuristic) -> Self {
+        let distance: i32 = heuristic.estimate(&puzzle_state, goal);
+        Self {
+            puzzle_state,
+            length: 0,
+            distance,
+            value: distance, // value is the distance + length
+            direction: Direction::None,
+            parent_node: None,
+            is_movable: false,
+            is_ending_node: goal.is_reached(&puzzle_state),
+        }
+    }
+
+    pub fn new_with_parent(node: Rc<RefCell<Self>>, direction: Direction) -> Self {
+        Self {
+            puzzle_state: {let x = node.as_ref().borrow().puzzle_state; x},
+            length: {let x = node.as_ref().borrow().length + 1; x},
+            distance: 0,
+            value: 0, // value is the distance + length
+            direction,
+            parent_node: Some(node),
+            is_movable: false,
+            is_ending_node: false,
+        }
+    }
+
+    /// Sums the distances every tile the goal constrains, except the movable piece, is away from its position in the goal.
+    pub fn manhattan_distance(puzzle_state: &[i8; N], goal: &Goal<N>) -> i32 {
+        let mut distance: i32 = 0;
+        for (position, current_num) in puzzle_state.iter().enumerate() {
+            if *current_num == movable_piece(N) {
+                continue;
+            }
+            if let Some(goal_position) = goal.position_of(*current_num) {
+                distance += goal.board().distance(position, goal_position) as i32;
+            }
+        }
+        distance
+    }
+
+    fn update(&mut self, goal: &Goal<N>, heuristic: &Heuristic) {
+        self.distance = heuristic.estimate(&self.puzzle_state, goal);
+        self.value = self.distance + self.length;
+        self.is_movable = true;
+    }
+}
+
+/// Finds where the movable piece is in the puzzle state.
+pub(crate) fn movable_piece_location<const N: usize>(puzzle_state: &[i8; N]) -> usize {
+    puzzle_state.iter().position(|value| *value == movable_piece(N)).unwrap()
+}
+
+pub(crate) trait FindMovableNodes<const N: usize> {
+    fn get_possible_nodes(&self, goal: &Goal<N>, heuristic: &Heuristic) -> Vec<Node<N>>;
+    fn left(&self, goal: &Goal<N>, heuristic: &Heuristic) -> Node<N>;
+    fn right(&self, goal: &Goal<N>, heuristic: &Heuristic) -> Node<N>;
+    fn up(&self, goal: &Goal<N>, heuristic: &Heuristic) -> Node<N>;
+    fn down(&self, goal: &Goal<N>, heuristic: &Heuristic) -> Node<N>;
+    fn move_towards(&self, direction: Direction, goal: &Goal<N>, heuristic: &Heuristic) -> Node<N>;
+    fn is_out_of_bounds(board: &Board, current_position: usize, direction: Direction) -> bool;
+    fn check_completion(node: &Node<N>, goal: &Goal<N>) -> bool;
+}
+
+impl<const N: usize> FindMovableNodes<N> for Rc<RefCell<Node<N>>> {
+    fn get_possible_nodes(&self, goal: &Goal<N>, heuristic: &Heuristic) -> Vec<Node<N>> {
+        match self.as_ref().borrow().direction {
+            Direction::Left => {
+                filter_movable_nodes!([self.left(goal, heuristic), self.up(goal, heuristic), self.down(goal, heuristic)])
+            },
+            Direction::Right => {
+                filter_movable_nodes!([self.right(goal, heuristic), self.up(goal, heuristic), self.down(goal, heuristic)])
+            }
+            Direction::Up => {
+                filter_movable_nodes!([self.left(goal, heuristic), self.right(goal, heuristic), self.up(goal, heuristic)])
+            },
+            Direction::Down => {
+                filter_movable_nodes!([self.left(goal, heuristic), self.right(goal, heuristic), self.down(goal, heuristic)])
+            },
+            Direction::None => {
+                filter_movable_nodes!([self.left(goal, heuristic), self.right(goal, heuristic), self.up(goal, heuristic), self.down(goal, heuristic)])
+            },
+        }
+    }
+
+    fn left(&self, goal: &Goal<N>, heuristic: &Heuristic) -> Node<N> {
+        self.move_towards(Direction::Left, goal, heuristic)
+    }
+
+    fn right(&self, goal: &Goal<N>, heuristic: &Heuristic) -> Node<N> {
+        self.move_towards(Direction::Right, goal, heuristic)
+    }
+
+    fn up(&self, goal: &Goal<N>, heuristic: &Heuristic) -> Node<N> {
+        self.move_towards(Direction::Up, goal, heuristic)
+    }
+
+    fn down(&self, goal: &Goal<N>, heuristic: &Heuristic) -> Node<N> {
+        self.move_towards(Direction::Down, goal, heuristic)
+    }
+
+    fn move_towards(&self, direction: Direction, goal: &Goal<N>, heuristic: &Heuristic) -> Node<N> {
+        let mut node = Node::new_with_parent(self.clone(), direction);
+
+        let movable_piece_location = movable_piece_location(&node.puzzle_state);
+
+        if !Self::is_out_of_bounds(goal.board(), movable_piece_location, direction) {
+            let target_location: usize = goal.board().neighbour(movable_piece_location, direction).unwrap();
+            node.puzzle_state.swap(movable_piece_location, target_location);
+            node.update(goal, heuristic);
+        }
+
+        if Self::check_completion(&node, goal) {
+            node.is_ending_node = true;
+        }
+
+        node
+    }
+
+    fn is_out_of_bounds(board: &Board, current_position: usize, direction: Direction) -> bool {
+        board.neighbour(current_position, direction).is_none()
+    }
+
+    fn check_completion(node: &Node<N>, goal: &Goal<N>) -> bool {
+        goal.is_reached(&node.puzzle_state)
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use crate::{movable_piece, replay_moves, replay_steps, simplify_moves, AStar, Board, Direction, DistanceTable, Goal, IllegalMove, SearchLimits, Solve, TOTAL_PUZZLE_SIZE};
+
+/// How a `SolutionOptimiser` finds the shortest moves between the two ends of a window.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum WindowSearch {
+    /// A* towards the end of the window, giving up on the window after expanding this many nodes.
+    AStar { max_expanded: u64 },
+    /// Looks the moves up in distance tables, one for every position of the movable piece at the
+    /// end of a window, built the first time they are needed. Puzzles of more than
+    /// `DistanceTable::MAX_TILES` tiles fall back to A*, giving up after expanding this many nodes.
+    DistanceTable { max_expanded: u64 },
+}
+
+/// Moves made shorter by a `SolutionOptimiser`.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct OptimisedMoves {
+    pub moves: Vec<Direction>,
+    pub original_length: usize,
+    /// The number of windows that were replaced by shorter moves.
+    pub replaced_windows: usize,
+}
+
+impl OptimisedMoves {
+    /// Gets the number of moves the optimiser saved.
+    pub fn saved(&self) -> usize {
+        self.original_length - self.moves.len()
+    }
+}
+
+/// Shortens moves, e.g. of a greedy or rule based solver, by replacing every window of moves
+/// between two of the puzzle states they pass through with the shortest moves between those states.
+/// It slides the window over the moves until no window of the configured size gets any shorter,
+/// so the moves end in the same puzzle state and every window of them is optimal.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Board, MoveNotation, SolutionOptimiser};
+///
+/// // going around the bottom right corner three times puts the tiles back
+/// let moves = MoveNotation::Blank.parse("LURDLURDLURDU").unwrap();
+/// let optimised = SolutionOptimiser::new(Board::square(3)).optimise(&[0, 1, 2, 3, 4, 5, 6, 7, 8], &moves).unwrap();
+///
+/// assert_eq!(MoveNotation::Blank.format(&optimised.moves), "U");
+/// assert_eq!(optimised.saved(), 12);
+/// ```
+pub struct SolutionOptimiser<const N: usize = TOTAL_PUZZLE_SIZE> {
+    board: Board,
+    window: usize,
+    window_search: WindowSearch,
+    /// The distance tables by the position of the movable piece in their goal.
+    distance_tables: HashMap<usize, Option<DistanceTable<N>>>,
+}
+
+impl<const N: usize> SolutionOptimiser<N> {
+    pub const DEFAULT_WINDOW: usize = 16;
+    pub const DEFAULT_MAX_EXPANDED: u64 = 100_000;
+
+    pub fn new(board: Board) -> SolutionOptimiser<N> {
+        SolutionOptimiser {
+            board,
+            window: Self::DEFAULT_WINDOW,
+            window_search: WindowSearch::AStar { max_expanded: Self::DEFAULT_MAX_EXPANDED },
+            distance_tables: HashMap::new(),
+        }
+    }
+
+    /// Sets the number of moves in a window.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the window has fewer than two moves, as a single move can't get shorter.
+    pub fn with_window(mut self, window: usize) -> SolutionOptimiser<N> {
+        assert!(window >= 2, "a window needs at least two moves");
+        self.window = window;
+        self
+    }
+
+    pub fn with_window_search(mut self, window_search: WindowSearch) -> SolutionOptimiser<N> {
+        self.window_search = window_search;
+        self
+    }
+
+    /// Shortens the moves from the puzzle state, or gets the first move that can't be made.
+    pub fn optimise(&mut self, puzzle_state: &[i8; N], moves: &[Direction]) -> Result<OptimisedMoves, IllegalMove<N>> {
+        replay_steps(&self.board, puzzle_state, moves)?;
+        let original_length: usize = moves.len();
+        let mut moves: Vec<Direction> = simplify_moves(moves);
+        let mut replaced_windows: usize = 0;
+
+        let mut is_improved: bool = true;
+        while is_improved {
+            is_improved = false;
+            let mut steps: Vec<[i8; N]> = replay_steps(&self.board, puzzle_state, &moves).expect("shortened moves stay on the board");
+            let mut start: usize = 0;
+            while start + 2 <= moves.len() {
+                let end: usize = (start + self.window).min(moves.len());
+                match self.shortest_moves(&steps[start], &steps[end], end - start - 1) {
+                    Some(shorter_moves) => {
+                        moves.splice(start..end, shorter_moves);
+                        steps = replay_steps(&self.board, puzzle_state, &moves).expect("shortened moves stay on the board");
+                        replaced_windows += 1;
+                        is_improved = true;
+                    }
+                    None => start += 1,
+                }
+            }
+        }
+
+        Ok(OptimisedMoves {
+            moves,
+            original_length,
+            replaced_windows,
+        })
+    }
+
+    /// Gets the shortest moves from one puzzle state to the other when they take at most `max_length` moves.
+    fn shortest_moves(&mut self, from: &[i8; N], to: &[i8; N], max_length: usize) -> Option<Vec<Direction>> {
+        let max_expanded: u64 = match self.window_search {
+            WindowSearch::AStar { max_expanded } => max_expanded,
+            WindowSearch::DistanceTable { max_expanded } => match self.shortest_moves_from_table(from, to, max_length) {
+                Ok(moves) => return moves,
+                Err(()) => max_expanded,
+            },
+        };
+        let goal: Goal<N> = Goal::new(self.board, *to).expect("every puzzle state on the way is a valid goal");
+        let limits: SearchLimits = SearchLimits { max_expanded: Some(max_expanded), ..SearchLimits::none() };
+        let solved_state = AStar::with_goal(goal).with_limits(limits).solve(&mut from.clone()).ok()?;
+        (solved_state.moves.len() <= max_length).then_some(solved_state.moves)
+    }
+
+    /// Relabels the tiles so the end of the window becomes the goal of a distance table, which
+    /// leaves the positions of the movable piece and so the moves between them unchanged, and then
+    /// follows the distances down. Fails when the puzzle has too many tiles for a table.
+    fn shortest_moves_from_table(&mut self, from: &[i8; N], to: &[i8; N], max_length: usize) -> Result<Option<Vec<Direction>>, ()> {
+        let movable_piece_position: usize = to.iter().position(|tile| *tile == movable_piece(N)).unwrap();
+        let board: Board = self.board;
+        let distance_table: &DistanceTable<N> = self
+            .distance_tables
+            .entry(movable_piece_position)
+            .or_insert_with(|| {
+                let mut tiles = 0..movable_piece(N);
+                let goal_state: [i8; N] = core::array::from_fn(|position| if position == movable_piece_position { movable_piece(N) } else { tiles.next().unwrap() });
+                DistanceTable::new(&Goal::new(board, goal_state).unwrap())
+            })
+            .as_ref()
+            .ok_or(())?;
+
+        let goal_state: &[i8; N] = distance_table.goal().puzzle_state();
+        let mut relabelled_tiles: [i8; N] = [0; N];
+        for (position, tile) in to.iter().enumerate() {
+            relabelled_tiles[*tile as usize] = goal_state[position];
+        }
+        let mut puzzle_state: [i8; N] = from.map(|tile| relabelled_tiles[tile as usize]);
+        let mut distance: usize = match distance_table.distance(&puzzle_state) {
+            Some(distance) if distance <= max_length => distance,
+            _ => return Ok(None),
+        };
+
+        let mut moves: Vec<Direction> = Vec::with_capacity(distance);
+        while distance > 0 {
+            let (direction, next_state) = Direction::MOVES
+                .iter()
+                .filter_map(|direction| replay_moves(&board, &puzzle_state, &[*direction]).ok().map(|next_state| (*direction, next_state)))
+                .find(|(_, next_state)| distance_table.distance(next_state) == Some(distance - 1))
+                .expect("a state next to every state is one move closer to the goal");
+            moves.push(direction);
+            puzzle_state = next_state;
+            distance -= 1;
+        }
+        Ok(Some(moves))
+    }
+}
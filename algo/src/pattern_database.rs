@@ -0,0 +1,151 @@
+use std::collections::VecDeque;
+
+use crate::{movable_piece, Direction, Goal, Symmetry, TOTAL_PUZZLE_SIZE};
+
+const UNREACHED: u8 = u8::MAX;
+
+/// The optimal number of moves to bring a pattern of tiles and the movable piece to their goal
+/// positions, when the other tiles can't be told apart. Every move counts, including the moves
+/// of the other tiles, so the lookup never overestimates the moves to the goal.
+///
+/// When the goal has a `Symmetry`, the mirror of a puzzle state needs as many moves as the state
+/// itself, while the mirror holds other tiles at the positions of the pattern. The estimate is
+/// the higher of the lookups of the state and its mirror, so a single table gives two patterns.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Goal, PatternDatabase};
+///
+/// // the top row of the 8-puzzle, and the left column through the mirror
+/// let pattern_database: PatternDatabase = PatternDatabase::new(&Goal::default(), &[0, 1, 2]).unwrap();
+/// let puzzle_state: [i8; 9] = [0, 1, 2, 6, 4, 5, 3, 8, 7];
+///
+/// assert_eq!(pattern_database.lookup(&puzzle_state), 1);
+/// assert_eq!(pattern_database.estimate(&puzzle_state), 15);
+/// ```
+pub struct PatternDatabase<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    /// The pattern tiles followed by the movable piece.
+    tiles: Vec<i8>,
+    symmetry: Option<Symmetry<N>>,
+    distances: Vec<u8>,
+}
+
+impl<const N: usize> PatternDatabase<N> {
+    /// The most slots a table can have, one byte each.
+    pub const MAX_SIZE: usize = 1 << 28;
+
+    /// Builds the table for the pattern tiles, or gets `None` when it would have more than `MAX_SIZE` slots.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the goal doesn't place a pattern tile or the movable piece.
+    pub fn new(goal: &Goal<N>, pattern: &[i8]) -> Option<PatternDatabase<N>> {
+        let mut tiles: Vec<i8> = pattern.iter().copied().filter(|tile| *tile != movable_piece(N)).collect::<Vec<_>>();
+        tiles.sort();
+        tiles.dedup();
+        tiles.push(movable_piece(N));
+        let size: usize = (N + 1 - tiles.len()..=N).try_fold(1usize, |size, base| size.checked_mul(base))?;
+        if size > Self::MAX_SIZE {
+            return None;
+        }
+        let goal_positions: Vec<usize> = tiles
+            .iter()
+            .map(|tile| goal.position_of(*tile).unwrap_or_else(|| panic!("the goal doesn't place tile {}", tile)))
+            .collect::<Vec<_>>();
+
+        let mut distances: Vec<u8> = vec![UNREACHED; size];
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        let goal_index: usize = rank::<N>(&goal_positions);
+        distances[goal_index] = 0;
+        queue.push_back(goal_index);
+
+        while let Some(index) = queue.pop_front() {
+            let distance: u8 = distances[index];
+            let positions: Vec<usize> = unrank::<N>(index, tiles.len());
+            let movable_piece_location: usize = positions[tiles.len() - 1];
+            for direction in Direction::MOVES {
+                let Some(target) = goal.board().neighbour(movable_piece_location, direction) else {
+                    continue;
+                };
+                let mut next_positions: Vec<usize> = positions.clone();
+                if let Some(moved) = next_positions.iter().position(|position| *position == target) {
+                    next_positions[moved] = movable_piece_location;
+                }
+                next_positions[tiles.len() - 1] = target;
+                let next_index: usize = rank::<N>(&next_positions);
+                if distances[next_index] == UNREACHED {
+                    distances[next_index] = distance + 1;
+                    queue.push_back(next_index);
+                }
+            }
+        }
+
+        Some(PatternDatabase {
+            goal: goal.clone(),
+            tiles,
+            symmetry: Symmetry::diagonal(goal),
+            distances,
+        })
+    }
+
+    pub fn goal(&self) -> &Goal<N> {
+        &self.goal
+    }
+
+    /// Gets the pattern tiles in increasing order, without the movable piece.
+    pub fn pattern(&self) -> &[i8] {
+        &self.tiles[..self.tiles.len() - 1]
+    }
+
+    /// Gets the symmetry whose mirrored lookups the estimate takes into account, if the goal has one.
+    pub fn symmetry(&self) -> Option<&Symmetry<N>> {
+        self.symmetry.as_ref()
+    }
+
+    /// Gets the number of bytes the distances take.
+    pub fn size(&self) -> usize {
+        self.distances.len()
+    }
+
+    /// Gets the moves the pattern tiles and the movable piece of the puzzle state need.
+    pub fn lookup(&self, puzzle_state: &[i8; N]) -> i32 {
+        let mut positions: Vec<usize> = vec![0; self.tiles.len()];
+        for (position, tile) in puzzle_state.iter().enumerate() {
+            if let Some(index) = self.tiles.iter().position(|pattern_tile| pattern_tile == tile) {
+                positions[index] = position;
+            }
+        }
+        self.distances[rank::<N>(&positions)] as i32
+    }
+
+    /// Gets the higher of the lookups of the puzzle state and its mirror.
+    pub fn estimate(&self, puzzle_state: &[i8; N]) -> i32 {
+        let estimate: i32 = self.lookup(puzzle_state);
+        match &self.symmetry {
+            Some(symmetry) => estimate.max(self.lookup(&symmetry.mirror(puzzle_state))),
+            None => estimate,
+        }
+    }
+}
+
+/// Numbers the ways to place the tiles on distinct positions out of `N`.
+fn rank<const N: usize>(positions: &[usize]) -> usize {
+    let mut index: usize = 0;
+    for (i, position) in positions.iter().enumerate() {
+        let used_before: usize = positions[..i].iter().filter(|used| *used < position).count();
+        index = index * (N - i) + position - used_before;
+    }
+    index
+}
+
+fn unrank<const N: usize>(mut index: usize, len: usize) -> Vec<usize> {
+    let mut codes: Vec<usize> = vec![0; len];
+    for i in (0..len).rev() {
+        codes[i] = index % (N - i);
+        index /= N - i;
+    }
+    let mut free_positions: Vec<usize> = (0..N).collect::<Vec<_>>();
+    codes.into_iter().map(|code| free_positions.remove(code)).collect::<Vec<_>>()
+}
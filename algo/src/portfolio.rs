@@ -0,0 +1,160 @@
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{CancelFlag, SearchLimits, SearchStats, Solve, SolveError, SolvedState, TOTAL_PUZZLE_SIZE};
+
+/// Creates a solver on the thread that runs it, with the limits the portfolio gives it.
+type CreateSolver<const N: usize> = Box<dyn Fn(SearchLimits) -> Box<dyn Solve<N>> + Send + Sync>;
+
+struct Entry<const N: usize> {
+    name: String,
+    is_optimal: bool,
+    create_solver: CreateSolver<N>,
+}
+
+/// Races a set of solvers on their own threads for the same puzzle state.
+///
+/// The first optimal solver to finish wins and the others are cancelled. Solvers that are not
+/// optimal only win when no optimal solver finishes before the deadline, in which case the
+/// shortest answer found so far is returned. The name of the winner is kept until the next solve.
+///
+/// The solvers are created on their threads, as they can't move between threads once created,
+/// and have to keep the limits they are created with so they can be cancelled.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{AStar, Heuristic, IdaStar, Portfolio, Solve};
+///
+/// let mut portfolio: Portfolio = Portfolio::new()
+///     .with_solver("astar", |limits| AStar::new().with_limits(limits))
+///     .with_solver("idastar", |limits| IdaStar::new().with_heuristic(Heuristic::LinearConflict).with_limits(limits));
+/// let solved_state = portfolio.solve(&mut [5, 2, 3, 1, 8, 4, 6, 0, 7]).unwrap();
+///
+/// assert_eq!(solved_state.moves.len(), 22);
+/// assert!(portfolio.winner().is_some());
+/// ```
+pub struct Portfolio<const N: usize = TOTAL_PUZZLE_SIZE> {
+    entries: Vec<Entry<N>>,
+    deadline: Option<Duration>,
+    winner: Option<String>,
+}
+
+impl<const N: usize> Portfolio<N> {
+    pub fn new() -> Portfolio<N> {
+        Portfolio {
+            entries: Vec::new(),
+            deadline: None,
+            winner: None,
+        }
+    }
+
+    /// Adds a solver that always finds a shortest solution.
+    pub fn with_solver<S, F>(self, name: &str, create_solver: F) -> Portfolio<N>
+    where
+        S: Solve<N> + 'static,
+        F: Fn(SearchLimits) -> S + Send + Sync + 'static,
+    {
+        self.with_entry(name, true, create_solver)
+    }
+
+    /// Adds a solver whose solutions may be longer than needed.
+    pub fn with_inexact_solver<S, F>(self, name: &str, create_solver: F) -> Portfolio<N>
+    where
+        S: Solve<N> + 'static,
+        F: Fn(SearchLimits) -> S + Send + Sync + 'static,
+    {
+        self.with_entry(name, false, create_solver)
+    }
+
+    fn with_entry<S, F>(mut self, name: &str, is_optimal: bool, create_solver: F) -> Portfolio<N>
+    where
+        S: Solve<N> + 'static,
+        F: Fn(SearchLimits) -> S + Send + Sync + 'static,
+    {
+        self.entries.push(Entry {
+            name: name.to_string(),
+            is_optimal,
+            create_solver: Box::new(move |limits| Box::new(create_solver(limits))),
+        });
+        self
+    }
+
+    /// Stops every solver after the given time, returning the best answer found until then.
+    pub fn with_deadline(mut self, deadline: Duration) -> Portfolio<N> {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Gets the name of the solver whose answer the last solve returned.
+    pub fn winner(&self) -> Option<&str> {
+        self.winner.as_deref()
+    }
+}
+
+impl<const N: usize> Default for Portfolio<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Solve<N> for Portfolio<N> {
+    fn solve(&mut self, puzzle_state: &mut [i8; N]) -> Result<SolvedState<N>, SolveError> {
+        let started: Instant = Instant::now();
+        let cancel: CancelFlag = CancelFlag::new();
+        let limits: SearchLimits = SearchLimits { cancel: Some(cancel.clone()), ..SearchLimits::none() };
+        let (sender, receiver) = mpsc::channel::<(usize, Result<SolvedState<N>, SolveError>)>();
+        let start: [i8; N] = *puzzle_state;
+        self.winner = None;
+
+        let (winner, result) = thread::scope(|scope| {
+            for (index, entry) in self.entries.iter().enumerate() {
+                let (sender, limits) = (sender.clone(), limits.clone());
+                scope.spawn(move || {
+                    let result = (entry.create_solver)(limits).solve(&mut start.clone());
+                    // the portfolio stops listening once it has its answer
+                    sender.send((index, result)).ok();
+                });
+            }
+            drop(sender);
+
+            let mut best: Option<(usize, SolvedState<N>)> = None;
+            let mut stats: SearchStats = SearchStats::default();
+            let outcome: Result<(), SolveError> = loop {
+                let message = match self.deadline {
+                    Some(deadline) => receiver.recv_timeout(deadline.saturating_sub(started.elapsed())),
+                    None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+                match message {
+                    Ok((index, Ok(solved_state))) => {
+                        let is_optimal: bool = self.entries[index].is_optimal;
+                        if best.as_ref().is_none_or(|(_, best)| solved_state.moves.len() < best.moves.len()) {
+                            best = Some((index, solved_state));
+                        }
+                        if is_optimal {
+                            break Ok(());
+                        }
+                    }
+                    Ok((_, Err(error @ (SolveError::Unsolvable | SolveError::CheckpointMismatch)))) => break Err(error),
+                    Ok((_, Err(SolveError::LimitReached(solver_stats)))) => {
+                        stats.expanded += solver_stats.expanded;
+                        stats.generated += solver_stats.generated;
+                    }
+                    Err(_) => break Ok(()),
+                }
+            };
+            cancel.cancel();
+
+            stats.elapsed = started.elapsed();
+            match (outcome, best) {
+                (Err(error), _) => (None, Err(error)),
+                (Ok(()), Some((index, solved_state))) => (Some(index), Ok(solved_state)),
+                (Ok(()), None) => (None, Err(SolveError::LimitReached(stats))),
+            }
+        });
+
+        self.winner = winner.map(|index| self.entries[index].name.clone());
+        result
+    }
+}
@@ -0,0 +1,122 @@
+extern crate rand;
+
+use std::fmt;
+
+use crate::{movable_piece, Board, Goal, ScrambleMode, Scrambler, WILDCARD};
+
+/// Shuffles a puzzle state that can reach the default goal, from a random seed.
+pub fn create_puzzle_state<const N: usize>() -> [i8; N] {
+    Scrambler::new(rand::random()).scramble(ScrambleMode::Shuffle).expect("a shuffle always finds a puzzle state")
+}
+
+/// Checks whether the default goal can be reached from the given puzzle state.
+pub fn check_solvability<const N: usize>(puzzle_state: &[i8; N]) -> bool {
+    Goal::default().is_reachable_from(puzzle_state)
+}
+
+pub fn create_array_with_increasing_value<const N: usize>() -> [i8; N] {
+    core::array::from_fn(|i: usize| i as i8)
+}
+
+/// Reasons a written puzzle state can't be read.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum ParseStateError {
+    /// The puzzle state has another number of tiles than the puzzle.
+    WrongTileCount(usize, usize),
+    InvalidTile(String),
+    DuplicateTile(String),
+}
+
+impl fmt::Display for ParseStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseStateError::WrongTileCount(found, expected) => write!(f, "found {} tiles, expected {}", found, expected),
+            ParseStateError::InvalidTile(tile) => write!(f, "'{}' is not a tile of the puzzle", tile),
+            ParseStateError::DuplicateTile(tile) => write!(f, "tile '{}' appears more than once", tile),
+        }
+    }
+}
+
+impl std::error::Error for ParseStateError {}
+
+/// Reads a puzzle state written the way people number the tiles: from `1` in reading order,
+/// with `0` or `_` for the movable piece. Tiles are separated by whitespace or commas.
+///
+/// # Examples
+///
+/// ```
+/// let puzzle_state: [i8; 9] = algo::parse_puzzle_state("6 3 4 2 _ 5 7 1 8").unwrap();
+///
+/// assert_eq!(puzzle_state, [5, 2, 3, 1, 8, 4, 6, 0, 7]);
+/// ```
+pub fn parse_puzzle_state<const N: usize>(text: &str) -> Result<[i8; N], ParseStateError> {
+    let labels: Vec<&str> = text.split(|c: char| c.is_whitespace() || c == ',').filter(|label| !label.is_empty()).collect::<Vec<_>>();
+    let tiles: Vec<i8> = parse_tiles(&labels, N)?;
+    Ok(tiles.try_into().expect("the tile count was checked"))
+}
+
+/// Reads the labels of a puzzle with `len` tiles into tiles.
+pub(crate) fn parse_tiles(labels: &[&str], len: usize) -> Result<Vec<i8>, ParseStateError> {
+    let numbers: Vec<usize> = labels
+        .iter()
+        .map(|label| match *label {
+            "_" => Ok(0),
+            _ => label.parse::<usize>().map_err(|_| ParseStateError::InvalidTile(label.to_string())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    tiles_from_labels(&numbers, len)
+}
+
+/// Turns the numbers people use for the tiles of a puzzle with `len` tiles back into tiles, the opposite of `tile_label`.
+pub(crate) fn tiles_from_labels(labels: &[usize], len: usize) -> Result<Vec<i8>, ParseStateError> {
+    if labels.len() != len {
+        return Err(ParseStateError::WrongTileCount(labels.len(), len));
+    }
+
+    let mut tiles: Vec<i8> = vec![0; len];
+    let mut is_used: Vec<bool> = vec![false; len];
+    for (position, label) in labels.iter().enumerate() {
+        let tile: i8 = match *label {
+            0 => movable_piece(len),
+            number if number < len => number as i8 - 1,
+            _ => return Err(ParseStateError::InvalidTile(label.to_string())),
+        };
+        if std::mem::replace(&mut is_used[tile as usize], true) {
+            return Err(ParseStateError::DuplicateTile(label.to_string()));
+        }
+        tiles[position] = tile;
+    }
+    Ok(tiles)
+}
+
+/// Gets the number people use for a tile, counting from `1` with `0` for the movable piece.
+pub fn tile_label<const N: usize>(tile: i8) -> usize {
+    if tile == movable_piece(N) { 0 } else { tile as usize + 1 }
+}
+
+/// Writes the puzzle state on a single line the way `parse_puzzle_state` reads it.
+pub fn format_puzzle_state<const N: usize>(puzzle_state: &[i8; N]) -> String {
+    puzzle_state.iter().map(|tile| tile_label::<N>(*tile).to_string()).collect::<Vec<_>>().join(" ")
+}
+
+/// Draws the puzzle state as rows of right aligned tiles with `_` for the movable piece
+/// and `*` for the positions of a partial goal that hold a `WILDCARD`.
+pub fn draw_puzzle_state<const N: usize>(puzzle_state: &[i8; N], board: &Board) -> String {
+    let cell_width: usize = (N - 1).to_string().len();
+    puzzle_state
+        .chunks(board.width())
+        .map(|row| {
+            row.iter()
+                .map(|tile| match *tile {
+                    WILDCARD => format!("{:>width$}", "*", width = cell_width),
+                    tile if tile == movable_piece(N) => format!("{:>width$}", "_", width = cell_width),
+                    tile => format!("{:>width$}", tile_label::<N>(tile), width = cell_width),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
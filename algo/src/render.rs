@@ -0,0 +1,188 @@
+use std::fmt::Write;
+
+use crate::{movable_piece, tile_label, Board, Direction, WILDCARD};
+
+const BACKGROUND: [u8; 3] = [0x33, 0x33, 0x33];
+const BLOCKED: [u8; 3] = [0x11, 0x11, 0x11];
+const TILE: [u8; 3] = [0xf0, 0xd9, 0xa0];
+const LABEL: [u8; 3] = [0x33, 0x33, 0x33];
+
+/// The digits `0` to `9` as 3 by 5 pixels, the top row in the highest bits.
+const DIGITS: [u16; 10] = [
+    0b111_101_101_101_111,
+    0b010_110_010_010_111,
+    0b111_001_111_100_111,
+    0b111_001_111_001_111,
+    0b101_101_111_001_001,
+    0b111_100_111_001_111,
+    0b111_100_111_101_111,
+    0b111_001_010_010_010,
+    0b111_101_111_101_111,
+    0b111_101_111_001_111,
+];
+
+/// Draws puzzle states as images, with the tile numbers on the tiles, the movable piece left
+/// empty and blocked positions darker than the board.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Board, Renderer};
+///
+/// let renderer: Renderer = Renderer::new(Board::square(2)).with_tile_size(10);
+/// let ppm: Vec<u8> = renderer.ppm(&[0, 1, 2, 3]);
+///
+/// assert!(ppm.starts_with(b"P6\n20 20\n255\n"));
+/// assert_eq!(ppm.len(), 13 + 20 * 20 * 3);
+/// assert!(renderer.svg(&[0, 1, 2, 3]).contains(">3</text>"));
+/// ```
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Renderer {
+    board: Board,
+    tile_size: usize,
+}
+
+impl Renderer {
+    pub const DEFAULT_TILE_SIZE: usize = 64;
+
+    pub fn new(board: Board) -> Renderer {
+        Renderer { board, tile_size: Self::DEFAULT_TILE_SIZE }
+    }
+
+    /// Sets the width and height of every tile in pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the tile size is 0.
+    pub fn with_tile_size(mut self, tile_size: usize) -> Renderer {
+        assert!(tile_size > 0, "tiles have to be at least one pixel");
+        self.tile_size = tile_size;
+        self
+    }
+
+    /// Gets the width and height of an image of a puzzle state in pixels.
+    pub fn size(&self) -> (usize, usize) {
+        (self.board.width() * self.tile_size, self.board.height() * self.tile_size)
+    }
+
+    /// Draws the puzzle state as an SVG document.
+    pub fn svg<const N: usize>(&self, puzzle_state: &[i8; N]) -> String {
+        let (width, height) = self.size();
+        let mut svg: String = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#, width, height);
+        svg.push('\n');
+        self.write_svg_tiles(&mut svg, puzzle_state);
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Draws the puzzle states next to each other in a single SVG document, `columns` to a row,
+    /// each with its step number and the move that led to it underneath.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `columns` is 0.
+    pub fn sprite_sheet<const N: usize>(&self, puzzle_states: &[[i8; N]], moves: &[Direction], columns: usize) -> String {
+        assert!(columns > 0, "a sprite sheet needs at least one column");
+        let (width, height) = self.size();
+        let (cell_width, cell_height) = (width + self.tile_size / 2, height + self.tile_size);
+        let rows: usize = puzzle_states.len().div_ceil(columns);
+        let (sheet_width, sheet_height) = (columns.min(puzzle_states.len()) * cell_width, rows * cell_height);
+        let mut svg: String = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#, sheet_width, sheet_height);
+        svg.push('\n');
+        for (step, puzzle_state) in puzzle_states.iter().enumerate() {
+            let (x, y) = ((step % columns) * cell_width, (step / columns) * cell_height);
+            writeln!(svg, r#"<g transform="translate({},{})">"#, x, y).unwrap();
+            self.write_svg_tiles(&mut svg, puzzle_state);
+            let caption: String = match step.checked_sub(1).and_then(|index| moves.get(index)) {
+                Some(direction) => format!("{} {}", step, direction),
+                None => step.to_string(),
+            };
+            writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="sans-serif" font-size="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                width / 2,
+                height + self.tile_size / 2,
+                self.tile_size * 2 / 5,
+                caption
+            )
+            .unwrap();
+            svg.push_str("</g>\n");
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn write_svg_tiles<const N: usize>(&self, svg: &mut String, puzzle_state: &[i8; N]) {
+        let (width, height) = self.size();
+        let gap: usize = self.tile_size / 16;
+        writeln!(svg, r#"<rect width="{}" height="{}" fill="{}"/>"#, width, height, hex(BACKGROUND)).unwrap();
+        for (position, tile) in puzzle_state.iter().enumerate() {
+            let (x, y) = (self.board.column(position) * self.tile_size, self.board.row(position) * self.tile_size);
+            let size: usize = self.tile_size - 2 * gap;
+            if self.board.is_blocked(position) {
+                writeln!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#, x, y, self.tile_size, self.tile_size, hex(BLOCKED)).unwrap();
+            }
+            if *tile == movable_piece(N) || *tile == WILDCARD {
+                continue;
+            }
+            writeln!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}" fill="{}"/>"#, x + gap, y + gap, size, size, gap * 2, hex(TILE)).unwrap();
+            writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="sans-serif" font-size="{}" text-anchor="middle" dominant-baseline="central" fill="{}">{}</text>"#,
+                x + self.tile_size / 2,
+                y + self.tile_size / 2,
+                self.tile_size * 2 / 5,
+                hex(LABEL),
+                tile_label::<N>(*tile)
+            )
+            .unwrap();
+        }
+    }
+
+    /// Draws the puzzle state as a binary PPM image.
+    pub fn ppm<const N: usize>(&self, puzzle_state: &[i8; N]) -> Vec<u8> {
+        let (width, height) = self.size();
+        let mut pixels: Vec<[u8; 3]> = vec![BACKGROUND; width * height];
+        let mut fill = |x: usize, y: usize, size_x: usize, size_y: usize, colour: [u8; 3]| {
+            for row in y..y + size_y {
+                pixels[row * width + x..row * width + x + size_x].fill(colour);
+            }
+        };
+        let gap: usize = self.tile_size / 16;
+        for (position, tile) in puzzle_state.iter().enumerate() {
+            let (x, y) = (self.board.column(position) * self.tile_size, self.board.row(position) * self.tile_size);
+            if self.board.is_blocked(position) {
+                fill(x, y, self.tile_size, self.tile_size, BLOCKED);
+            }
+            if *tile == movable_piece(N) || *tile == WILDCARD {
+                continue;
+            }
+            fill(x + gap, y + gap, self.tile_size - 2 * gap, self.tile_size - 2 * gap, TILE);
+
+            // digits of 3 by 5 pixels with a pixel between them, scaled to about a third of the tile
+            let digits: Vec<usize> = tile_label::<N>(*tile).to_string().bytes().map(|digit| (digit - b'0') as usize).collect::<Vec<_>>();
+            let scale: usize = (self.tile_size / 16).max(1);
+            let (text_width, text_height) = ((digits.len() * 4 - 1) * scale, 5 * scale);
+            if text_width > self.tile_size || text_height > self.tile_size {
+                continue;
+            }
+            let (left, top) = (x + (self.tile_size - text_width) / 2, y + (self.tile_size - text_height) / 2);
+            for (index, digit) in digits.iter().enumerate() {
+                for bit in 0..15 {
+                    if DIGITS[*digit] & (1 << (14 - bit)) != 0 {
+                        fill(left + (index * 4 + bit % 3) * scale, top + (bit / 3) * scale, scale, scale, LABEL);
+                    }
+                }
+            }
+        }
+
+        let mut ppm: Vec<u8> = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        ppm.extend(pixels.iter().flatten());
+        ppm
+    }
+}
+
+fn hex(colour: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", colour[0], colour[1], colour[2])
+}
@@ -0,0 +1,112 @@
+//! The JSON schema other tools can rely on, every change to it is a breaking change.
+//!
+//! | Type | JSON |
+//! |------|------|
+//! | `Board` | `{"width": 4, "height": 4, "topology": "torus", "blocked": [5, 10]}` |
+//! | puzzle state | `[1, 2, 3, 0]`, the tiles in reading order numbered from `1` with `0` for the movable piece |
+//! | goal state | `[1, null, 3, 0]`, like a puzzle state with `null` where a partial goal takes any tile |
+//! | `Direction` | `"L"`, `"R"`, `"U"` or `"D"`, where the movable piece goes |
+//! | moves | `"LURD"`, the letters of the directions in order |
+//! | `SearchStats` | `{"expanded": 12, "generated": 20, "elapsed_ms": 0.25}` |
+//! | `SolvedState` | `{"puzzle_state": [...], "moves": "LURD", "stats": {...}}` |
+//!
+//! The `topology` and `blocked` fields of a board are left out at their defaults and read as them when missing.
+//! `topology` is `"flat"`, `"cylinder"` or `"torus"`, `"flat"` by default. `blocked` lists the blocked
+//! positions in increasing order, numbered in reading order from `0`, none by default.
+//!
+//! Puzzle states and moves are plain arrays and vectors, so fields holding them take
+//! `#[serde(with = "algo::schema::puzzle_state")]` or `#[serde(with = "algo::schema::moves")]`.
+//!
+//! # Examples
+//!
+//! ```
+//! use algo::{Direction, SolvedState};
+//!
+//! let solved_state: SolvedState = SolvedState::new([0, 1, 2, 3, 4, 5, 6, 7, 8], vec![Direction::Right, Direction::Down]);
+//! let json = serde_json::to_value(&solved_state).unwrap();
+//!
+//! assert_eq!(json["puzzle_state"], serde_json::json!([1, 2, 3, 4, 5, 6, 7, 8, 0]));
+//! assert_eq!(json["moves"], "RD");
+//! ```
+
+/// Writes a puzzle state as the numbers people use for its tiles.
+pub mod puzzle_state {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::puzzle::tiles_from_labels;
+    use crate::tile_label;
+
+    pub fn serialize<S: Serializer, const N: usize>(puzzle_state: &[i8; N], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(puzzle_state.iter().map(|tile| tile_label::<N>(*tile)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[i8; N], D::Error> {
+        let labels: Vec<usize> = Vec::deserialize(deserializer)?;
+        let tiles: Vec<i8> = tiles_from_labels(&labels, N).map_err(D::Error::custom)?;
+        Ok(tiles.try_into().expect("the tile count was checked"))
+    }
+}
+
+/// Writes the puzzle state of a goal like a puzzle state, with `null` for every `WILDCARD`.
+pub mod goal_state {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::{movable_piece, tile_label, ParseStateError, WILDCARD};
+
+    pub fn serialize<S: Serializer, const N: usize>(goal_state: &[i8; N], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(goal_state.iter().map(|tile| (*tile != WILDCARD).then(|| tile_label::<N>(*tile))))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[i8; N], D::Error> {
+        let labels: Vec<Option<usize>> = Vec::deserialize(deserializer)?;
+        if labels.len() != N {
+            return Err(D::Error::custom(ParseStateError::WrongTileCount(labels.len(), N)));
+        }
+        let tiles: Vec<i8> = labels
+            .iter()
+            .map(|label| match *label {
+                None => Ok(WILDCARD),
+                Some(0) => Ok(movable_piece(N)),
+                Some(number) if number < N => Ok(number as i8 - 1),
+                Some(number) => Err(D::Error::custom(ParseStateError::InvalidTile(number.to_string()))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(tiles.try_into().expect("the tile count was checked"))
+    }
+}
+
+/// Writes moves as a string of letters in `MoveNotation::Blank`.
+pub mod moves {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::{Direction, MoveNotation};
+
+    pub fn serialize<S: Serializer>(moves: &[Direction], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&MoveNotation::Blank.format(moves))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Direction>, D::Error> {
+        let text: String = String::deserialize(deserializer)?;
+        MoveNotation::Blank.parse(&text).map_err(D::Error::custom)
+    }
+}
+
+/// Writes a duration as fractional milliseconds.
+pub(crate) mod milliseconds {
+    use std::time::Duration;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let milliseconds: f64 = f64::deserialize(deserializer)?;
+        Duration::try_from_secs_f64(milliseconds / 1000.0).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::node::movable_piece_location;
+use crate::{movable_piece, DistanceTable, Direction, Goal, Heuristic, IdaStar, Solve, TOTAL_PUZZLE_SIZE};
+
+/// How many random walks `ScrambleMode::ExactDistance` tries on puzzles too large for a `DistanceTable`.
+const EXACT_DISTANCE_ATTEMPTS: usize = 1000;
+
+/// How a `Scrambler` mixes up the goal.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum ScrambleMode {
+    /// Shuffles every tile and swaps two tiles when the shuffle can't reach the goal.
+    Shuffle,
+    /// Moves the movable piece this many times from the goal, never undoing the previous move.
+    RandomWalk(usize),
+    /// Picks any of the puzzle states that need exactly this many moves to reach the goal.
+    ExactDistance(usize),
+}
+
+/// Reasons a `Scrambler` can't create a puzzle state.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum ScrambleError {
+    /// No puzzle state needs this many moves, the argument is the largest distance there is.
+    DistanceTooLarge(usize, usize),
+    /// None of the random walks of this length turned out to need all of their moves.
+    DistanceNotFound(usize),
+}
+
+impl fmt::Display for ScrambleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScrambleError::DistanceTooLarge(distance, max_distance) => {
+                write!(f, "no puzzle state needs {} moves, the most is {}", distance, max_distance)
+            }
+            ScrambleError::DistanceNotFound(distance) => {
+                write!(f, "no puzzle state needing {} moves was found in {} attempts", distance, EXACT_DISTANCE_ATTEMPTS)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScrambleError {}
+
+/// Creates solvable puzzle states from a seed, so the same seed always gives the same puzzles.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{ScrambleMode, Scrambler};
+///
+/// let first: [i8; 9] = Scrambler::new(42).scramble(ScrambleMode::Shuffle).unwrap();
+/// let second: [i8; 9] = Scrambler::new(42).scramble(ScrambleMode::Shuffle).unwrap();
+///
+/// assert_eq!(first, second);
+/// ```
+pub struct Scrambler<const N: usize = TOTAL_PUZZLE_SIZE> {
+    rng: StdRng,
+    goal: Goal<N>,
+    distance_table: Option<DistanceTable<N>>,
+}
+
+impl<const N: usize> Scrambler<N> {
+    pub fn new(seed: u64) -> Scrambler<N> {
+        Scrambler::with_goal(seed, Goal::default())
+    }
+
+    pub fn with_goal(seed: u64, goal: Goal<N>) -> Scrambler<N> {
+        Scrambler {
+            rng: StdRng::seed_from_u64(seed),
+            goal,
+            distance_table: None,
+        }
+    }
+
+    pub fn scramble(&mut self, mode: ScrambleMode) -> Result<[i8; N], ScrambleError> {
+        match mode {
+            ScrambleMode::Shuffle => Ok(self.shuffle()),
+            ScrambleMode::RandomWalk(length) => Ok(self.random_walk(length)),
+            ScrambleMode::ExactDistance(distance) if N <= DistanceTable::<N>::MAX_TILES => self.exact_distance_from_table(distance),
+            ScrambleMode::ExactDistance(distance) => self.exact_distance_from_solver(distance),
+        }
+    }
+
+    /// Shuffles the tiles on the positions the movable piece can get to, the others never move.
+    fn shuffle(&mut self) -> [i8; N] {
+        let mut puzzle_state: [i8; N] = self.goal.completed_state();
+        let steps: Vec<Option<usize>> = self.goal.board().steps_from(movable_piece_location(&puzzle_state));
+        let positions: Vec<usize> = (0..N).filter(|position| steps[*position].is_some()).collect::<Vec<_>>();
+        let mut tiles: Vec<i8> = positions.iter().map(|position| puzzle_state[*position]).collect::<Vec<_>>();
+        tiles.shuffle(&mut self.rng);
+        for (position, tile) in positions.iter().zip(tiles) {
+            puzzle_state[*position] = tile;
+        }
+
+        if !self.goal.is_reachable_from(&puzzle_state) {
+            // swapping two tiles flips the parity of the permutation without moving the movable piece
+            let tiles: Vec<usize> = positions.iter().copied().filter(|position| puzzle_state[*position] != movable_piece(N)).take(2).collect::<Vec<_>>();
+            puzzle_state.swap(tiles[0], tiles[1]);
+        }
+        if !self.goal.is_reachable_from(&puzzle_state) {
+            // the tiles can't pass each other, so only a walk of the movable piece gets somewhere reachable
+            let length: usize = self.rng.gen_range(0..positions.len() * positions.len());
+            return self.random_walk(length);
+        }
+        puzzle_state
+    }
+
+    fn random_walk(&mut self, length: usize) -> [i8; N] {
+        let mut puzzle_state: [i8; N] = self.goal.completed_state();
+        let mut movable_piece_location: usize = movable_piece_location(&puzzle_state);
+        let mut previous_direction: Direction = Direction::None;
+
+        for _ in 0..length {
+            let neighbours: Vec<(Direction, usize)> = Direction::MOVES
+                .iter()
+                .filter_map(|direction| self.goal.board().neighbour(movable_piece_location, *direction).map(|target| (*direction, target)))
+                .collect::<Vec<_>>();
+            let forward: Vec<(Direction, usize)> = neighbours.iter().copied().filter(|(direction, _)| *direction != previous_direction.opposite()).collect::<Vec<_>>();
+            // at the end of a dead end the movable piece can only go back the way it came
+            let targets: Vec<(Direction, usize)> = if forward.is_empty() { neighbours } else { forward };
+            if targets.is_empty() {
+                break;
+            }
+            let (direction, target) = targets[self.rng.gen_range(0..targets.len())];
+
+            puzzle_state.swap(movable_piece_location, target);
+            movable_piece_location = target;
+            previous_direction = direction;
+        }
+        puzzle_state
+    }
+
+    /// Picks uniformly from the states a `DistanceTable` confirms to need exactly `distance` moves.
+    /// The table is built on first use and kept for later scrambles.
+    fn exact_distance_from_table(&mut self, distance: usize) -> Result<[i8; N], ScrambleError> {
+        let goal: &Goal<N> = &self.goal;
+        let distance_table: &DistanceTable<N> = self
+            .distance_table
+            .get_or_insert_with(|| DistanceTable::new(goal).expect("the puzzle fits a distance table"));
+
+        let candidates: Vec<[i8; N]> = distance_table.states_at(distance).collect::<Vec<_>>();
+        match candidates.choose(&mut self.rng) {
+            Some(puzzle_state) => Ok(*puzzle_state),
+            None => Err(ScrambleError::DistanceTooLarge(distance, distance_table.max_distance())),
+        }
+    }
+
+    /// Takes random walks of `distance` moves until an optimal solver confirms one can't be shortened.
+    fn exact_distance_from_solver(&mut self, distance: usize) -> Result<[i8; N], ScrambleError> {
+        let mut solver: IdaStar<N> = IdaStar::with_goal(self.goal.clone()).with_heuristic(Heuristic::LinearConflict);
+        for _ in 0..EXACT_DISTANCE_ATTEMPTS {
+            let puzzle_state: [i8; N] = self.random_walk(distance);
+            let solved_state = solver.solve(&mut puzzle_state.clone()).expect("a random walk can be walked back");
+            if solved_state.moves.len() == distance {
+                return Ok(puzzle_state);
+            }
+        }
+        Err(ScrambleError::DistanceNotFound(distance))
+    }
+}
@@ -0,0 +1,327 @@
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::time::Instant;
+
+use crate::node::movable_piece_location;
+use crate::{Direction, Goal, Heuristic, SearchLimits, SearchStats, Solve, SolveError, SolvedState, TOTAL_PUZZLE_SIZE};
+
+/// The value of a node whose subtree can't reach the goal within the memory.
+const UNREACHABLE: i32 = i32::MAX;
+
+/// Simplified memory-bounded A*, which behaves like A* until it holds `max_nodes` nodes and then makes
+/// room by forgetting the leaf with the highest value, the shallowest one between equal values.
+///
+/// The parent of a forgotten leaf remembers the value of the leaf, and every parent whose successors
+/// have all been generated takes the lowest value of its successors, so the tree keeps knowing how
+/// promising a forgotten subtree was and generates it again once it becomes the best option.
+///
+/// The solution is optimal as long as the nodes of a shortest path fit in `max_nodes`, when they
+/// don't the solver gives up with `SolveError::LimitReached`.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Heuristic, Solve, SmaStar};
+///
+/// let mut solver: SmaStar = SmaStar::new().with_heuristic(Heuristic::LinearConflict).with_max_nodes(200);
+/// let solved_state = solver.solve(&mut [5, 2, 3, 1, 8, 4, 6, 0, 7]).unwrap();
+///
+/// assert_eq!(solved_state.moves.len(), 22);
+/// ```
+pub struct SmaStar<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    heuristic: Heuristic,
+    limits: SearchLimits,
+    max_nodes: usize,
+}
+
+/// A node of the search tree, with a slot for every direction the movable piece can move in.
+struct TreeNode<const N: usize> {
+    puzzle_state: [i8; N],
+    length: i32,
+    value: i32,
+    parent: Option<usize>,
+    direction: Direction,
+    /// Whether the node reaches the goal, which keeps it open even without moves left so it is picked once it is the best.
+    is_goal: bool,
+    /// Whether the move in a direction stays on the board and doesn't undo the move that lead here.
+    is_move: [bool; 4],
+    is_generated: [bool; 4],
+    children: [Option<usize>; 4],
+    /// The value a successor had when it was forgotten.
+    forgotten: [Option<i32>; 4],
+}
+
+impl<const N: usize> TreeNode<N> {
+    fn has_child(&self) -> bool {
+        self.children.iter().any(Option::is_some)
+    }
+
+    fn is_fully_generated(&self) -> bool {
+        (0..4).all(|slot| !self.is_move[slot] || self.is_generated[slot])
+    }
+
+    /// Whether the node can still generate a successor that isn't in memory.
+    fn is_open(&self) -> bool {
+        (0..4).any(|slot| self.is_move[slot] && self.children[slot].is_none())
+    }
+}
+
+/// The tree and the ordered sets to find the best open node and the worst leaf.
+struct Tree<const N: usize> {
+    nodes: Vec<Option<TreeNode<N>>>,
+    free: Vec<usize>,
+    len: usize,
+    /// The open nodes, the lowest value first and the deepest between equal values.
+    open: BTreeSet<(i32, Reverse<i32>, usize)>,
+    /// The nodes without children in memory, the highest value last and the shallowest between equal values.
+    leaves: BTreeSet<(i32, Reverse<i32>, usize)>,
+}
+
+impl<const N: usize> Tree<N> {
+    fn node(&self, index: usize) -> &TreeNode<N> {
+        self.nodes[index].as_ref().unwrap()
+    }
+
+    fn key(&self, index: usize) -> (i32, Reverse<i32>, usize) {
+        let node: &TreeNode<N> = self.node(index);
+        (node.value, Reverse(node.length), index)
+    }
+
+    /// Takes the node out of the ordered sets before it changes.
+    fn unlist(&mut self, index: usize) {
+        let key = self.key(index);
+        self.open.remove(&key);
+        self.leaves.remove(&key);
+    }
+
+    /// Puts the node back in the ordered sets it belongs to after it changed.
+    fn list(&mut self, index: usize) {
+        let key = self.key(index);
+        if self.node(index).is_open() || self.node(index).is_goal {
+            self.open.insert(key);
+        }
+        if !self.node(index).has_child() {
+            self.leaves.insert(key);
+        }
+    }
+
+    fn insert(&mut self, node: TreeNode<N>) -> usize {
+        self.len += 1;
+        let index: usize = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.nodes.push(None);
+                self.nodes.len() - 1
+            }
+        };
+        self.nodes[index] = Some(node);
+        self.list(index);
+        index
+    }
+
+    /// Forgets the leaf with the highest value, other than the root and the given node,
+    /// telling whether there was one.
+    fn forget_worst_leaf(&mut self, keep: usize) -> bool {
+        let Some(&(_, _, leaf)) = self.leaves.iter().rev().find(|(_, _, index)| *index != keep && self.node(*index).parent.is_some()) else {
+            return false;
+        };
+        self.unlist(leaf);
+        let node: TreeNode<N> = self.nodes[leaf].take().unwrap();
+        self.free.push(leaf);
+        self.len -= 1;
+
+        let parent: usize = node.parent.unwrap();
+        self.unlist(parent);
+        let slot: usize = slot(node.direction);
+        let parent_node: &mut TreeNode<N> = self.nodes[parent].as_mut().unwrap();
+        parent_node.children[slot] = None;
+        parent_node.forgotten[slot] = Some(node.value);
+        self.list(parent);
+        true
+    }
+
+    /// Gives every fully generated node on the way to the root the lowest value of its successors.
+    fn back_up(&mut self, mut index: usize) {
+        loop {
+            let node: &TreeNode<N> = self.node(index);
+            if !node.is_fully_generated() {
+                return;
+            }
+            let lowest: i32 = (0..4)
+                .filter(|slot| node.is_move[*slot])
+                .map(|slot| match node.children[slot] {
+                    Some(child) => self.node(child).value,
+                    None => node.forgotten[slot].unwrap_or(UNREACHABLE),
+                })
+                .min()
+                .unwrap_or(UNREACHABLE);
+            if lowest == node.value {
+                return;
+            }
+            let parent: Option<usize> = node.parent;
+            self.unlist(index);
+            self.nodes[index].as_mut().unwrap().value = lowest;
+            self.list(index);
+            match parent {
+                Some(parent) => index = parent,
+                None => return,
+            }
+        }
+    }
+}
+
+fn slot(direction: Direction) -> usize {
+    Direction::MOVES.iter().position(|move_direction| *move_direction == direction).expect("a child is reached by a move")
+}
+
+impl<const N: usize> SmaStar<N> {
+    /// The number of nodes a solver keeps in memory when no other cap is given.
+    pub const DEFAULT_MAX_NODES: usize = 100_000;
+
+    pub fn new() -> SmaStar<N> {
+        SmaStar::with_goal(Goal::default())
+    }
+
+    pub fn with_goal(goal: Goal<N>) -> SmaStar<N> {
+        SmaStar {
+            goal,
+            heuristic: Heuristic::default(),
+            limits: SearchLimits::none(),
+            max_nodes: Self::DEFAULT_MAX_NODES,
+        }
+    }
+
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> SmaStar<N> {
+        self.heuristic = heuristic;
+        self
+    }
+
+    pub fn with_limits(mut self, limits: SearchLimits) -> SmaStar<N> {
+        self.limits = limits;
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics when the cap doesn't leave room for a node and a successor.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> SmaStar<N> {
+        assert!(max_nodes >= 2, "the search needs room for at least 2 nodes");
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    fn create_node(&self, puzzle_state: [i8; N], length: i32, value: i32, parent: Option<usize>, direction: Direction) -> TreeNode<N> {
+        let movable_piece_location: usize = movable_piece_location(&puzzle_state);
+        let is_move: [bool; 4] = Direction::MOVES
+            .map(|move_direction| move_direction != direction.opposite() && self.goal.board().neighbour(movable_piece_location, move_direction).is_some());
+        TreeNode {
+            puzzle_state,
+            length,
+            value,
+            parent,
+            direction,
+            is_goal: self.goal.is_reached(&puzzle_state),
+            is_move,
+            is_generated: [false; 4],
+            children: [None; 4],
+            forgotten: [None; 4],
+        }
+    }
+}
+
+impl<const N: usize> Default for SmaStar<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Solve<N> for SmaStar<N> {
+    fn solve(&mut self, puzzle_state: &mut [i8; N]) -> Result<SolvedState<N>, SolveError> {
+        if !self.goal.is_reachable_from(puzzle_state) {
+            return Err(SolveError::Unsolvable);
+        }
+
+        let started: Instant = Instant::now();
+        let mut stats: SearchStats = SearchStats::default();
+        let mut tree: Tree<N> = Tree { nodes: Vec::new(), free: Vec::new(), len: 0, open: BTreeSet::new(), leaves: BTreeSet::new() };
+        let root_value: i32 = self.heuristic.estimate(puzzle_state, &self.goal);
+        tree.insert(self.create_node(*puzzle_state, 0, root_value, None, Direction::None));
+
+        let best: Option<usize> = loop {
+            let Some(&(value, _, best)) = tree.open.first() else {
+                break None;
+            };
+            if value == UNREACHABLE {
+                break None;
+            }
+            if tree.node(best).is_goal {
+                break Some(best);
+            }
+            if self.limits.is_reached(&stats, started) {
+                stats.elapsed = started.elapsed();
+                return Err(SolveError::LimitReached(stats));
+            }
+            stats.expanded += 1;
+
+            // generate the next new successor, or else the forgotten one with the lowest value
+            let node: &TreeNode<N> = tree.node(best);
+            let slot: usize = (0..4)
+                .filter(|slot| node.is_move[*slot] && node.children[*slot].is_none())
+                .min_by_key(|slot| (node.is_generated[*slot], node.forgotten[*slot].unwrap_or(UNREACHABLE)))
+                .expect("an open node has a successor that isn't in memory");
+            let direction: Direction = Direction::MOVES[slot];
+            let movable_piece_location: usize = movable_piece_location(&node.puzzle_state);
+            let target: usize = self.goal.board().neighbour(movable_piece_location, direction).unwrap();
+            let mut child_state: [i8; N] = node.puzzle_state;
+            child_state.swap(movable_piece_location, target);
+            let length: i32 = node.length + 1;
+            stats.generated += 1;
+
+            // a child that can't reach the goal and has no room for its own successors, or no moves left, is a dead end
+            let has_moves: bool = Direction::MOVES.iter().any(|move_direction| *move_direction != direction.opposite() && self.goal.board().neighbour(target, *move_direction).is_some());
+            let value: i32 = if !self.goal.is_reached(&child_state) && (length as usize + 1 >= self.max_nodes || !has_moves) {
+                UNREACHABLE
+            } else {
+                (length + self.heuristic.estimate(&child_state, &self.goal)).max(node.value).max(node.forgotten[slot].unwrap_or(0))
+            };
+
+            let has_room: bool = tree.len < self.max_nodes || tree.forget_worst_leaf(best);
+            tree.unlist(best);
+            {
+                let node: &mut TreeNode<N> = tree.nodes[best].as_mut().unwrap();
+                node.is_generated[slot] = true;
+                if !has_room {
+                    node.forgotten[slot] = Some(UNREACHABLE);
+                }
+            }
+            if has_room {
+                let child: usize = tree.insert(self.create_node(child_state, length, value, Some(best), direction));
+                let node: &mut TreeNode<N> = tree.nodes[best].as_mut().unwrap();
+                node.children[slot] = Some(child);
+                node.forgotten[slot] = None;
+            }
+            tree.list(best);
+            tree.back_up(best);
+        };
+
+        stats.elapsed = started.elapsed();
+        let Some(best) = best else {
+            return Err(SolveError::LimitReached(stats));
+        };
+        let final_puzzle_state: [i8; N] = tree.node(best).puzzle_state;
+        let mut moves: Vec<Direction> = Vec::new();
+        let mut index: usize = best;
+        while let Some(parent) = tree.node(index).parent {
+            moves.push(tree.node(index).direction);
+            index = parent;
+        }
+        moves.reverse();
+
+        Ok(SolvedState {
+            puzzle_state: final_puzzle_state,
+            moves,
+            stats,
+        })
+    }
+}
@@ -0,0 +1,177 @@
+use crate::node::movable_piece_location;
+use crate::{Direction, Goal, Node, SolveError, TOTAL_PUZZLE_SIZE};
+
+/// A move sequence that takes a puzzle state to its goal.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Solution {
+    pub moves: Vec<Direction>,
+    pub cost: usize,
+}
+
+struct Frame<const N: usize> {
+    puzzle_state: [i8; N],
+    movable_piece_location: usize,
+    next_move: usize,
+}
+
+/// Iterates over the solutions of a puzzle state in order of increasing cost.
+///
+/// The search is an iterative deepening depth-first search, which only keeps the current path in memory.
+/// A solution ends at the first state that reaches the goal and never visits a state twice, so every
+/// solution is yielded exactly once. Moves that are transposed into a different order reach the same
+/// intermediate states along different paths, those are distinct move sequences and are each yielded.
+/// Within one cost the solutions come in the order of `Direction::MOVES`.
+pub struct Solutions<const N: usize = TOTAL_PUZZLE_SIZE> {
+    goal: Goal<N>,
+    puzzle_state: [i8; N],
+    only_optimal: bool,
+    bound: usize,
+    next_bound: Option<usize>,
+    /// No solution visits as many states, see `Goal::max_reachable_states`.
+    max_reachable_states: u64,
+    stack: Vec<Frame<N>>,
+    moves: Vec<Direction>,
+    is_solution_found: bool,
+    is_finished: bool,
+}
+
+impl<const N: usize> Solutions<N> {
+    /// Creates an iterator over every optimal solution of the given puzzle state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algo::{Goal, Solutions};
+    ///
+    /// let solutions = Solutions::optimal([8, 3, 2, 1, 0, 4, 6, 7, 5], &Goal::default()).unwrap();
+    ///
+    /// assert_eq!(solutions.count(), 2);
+    /// ```
+    pub fn optimal(puzzle_state: [i8; N], goal: &Goal<N>) -> Result<Solutions<N>, SolveError> {
+        Solutions::create(puzzle_state, goal, true)
+    }
+
+    /// Creates an iterator over every solution of the given puzzle state, shortest first.
+    /// Use `take` to get the k shortest solutions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algo::{Goal, Solutions};
+    ///
+    /// let solutions = Solutions::shortest([8, 3, 2, 1, 0, 4, 6, 7, 5], &Goal::default()).unwrap();
+    /// let costs = solutions.take(3).map(|solution| solution.cost).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(costs, vec![8, 8, 12]);
+    /// ```
+    pub fn shortest(puzzle_state: [i8; N], goal: &Goal<N>) -> Result<Solutions<N>, SolveError> {
+        Solutions::create(puzzle_state, goal, false)
+    }
+
+    fn create(puzzle_state: [i8; N], goal: &Goal<N>, only_optimal: bool) -> Result<Solutions<N>, SolveError> {
+        if !goal.is_reachable_from(&puzzle_state) {
+            return Err(SolveError::Unsolvable);
+        }
+        Ok(Solutions {
+            goal: goal.clone(),
+            puzzle_state,
+            only_optimal,
+            bound: 0,
+            next_bound: Some(Node::manhattan_distance(&puzzle_state, goal) as usize),
+            max_reachable_states: goal.max_reachable_states(),
+            stack: Vec::new(),
+            moves: Vec::new(),
+            is_solution_found: false,
+            is_finished: false,
+        })
+    }
+
+    /// Starts the next depth-first iteration with the smallest cost that was cut off in the last one.
+    fn start_iteration(&mut self) -> bool {
+        if self.only_optimal && self.is_solution_found {
+            return false;
+        }
+        match self.next_bound.take() {
+            Some(bound) if bound as u64 >= self.max_reachable_states => false,
+            Some(bound) => {
+                self.bound = bound;
+                self.stack.push(Frame {
+                    puzzle_state: self.puzzle_state,
+                    movable_piece_location: movable_piece_location(&self.puzzle_state),
+                    next_move: 0,
+                });
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<const N: usize> Iterator for Solutions<N> {
+    type Item = Solution;
+
+    fn next(&mut self) -> Option<Solution> {
+        if self.is_finished {
+            return None;
+        }
+        if self.goal.is_reached(&self.puzzle_state) {
+            // the empty solution ends at the starting state, so there are no others
+            self.is_finished = true;
+            return Some(Solution { moves: Vec::new(), cost: 0 });
+        }
+
+        loop {
+            if self.stack.is_empty() && !self.start_iteration() {
+                self.is_finished = true;
+                return None;
+            }
+
+            let frame: &mut Frame<N> = self.stack.last_mut().unwrap();
+            if frame.next_move == Direction::MOVES.len() {
+                self.stack.pop();
+                self.moves.pop();
+                continue;
+            }
+            let direction: Direction = Direction::MOVES[frame.next_move];
+            frame.next_move += 1;
+
+            if self.moves.last() == Some(&direction.opposite()) {
+                continue;
+            }
+            let Some(target) = self.goal.board().neighbour(frame.movable_piece_location, direction) else {
+                continue;
+            };
+            let mut puzzle_state: [i8; N] = frame.puzzle_state;
+            puzzle_state.swap(frame.movable_piece_location, target);
+            if self.stack.iter().any(|frame| frame.puzzle_state == puzzle_state) {
+                continue;
+            }
+
+            let cost: usize = self.moves.len() + 1;
+            let value: usize = cost + Node::manhattan_distance(&puzzle_state, &self.goal) as usize;
+            if value > self.bound {
+                self.next_bound = Some(self.next_bound.map_or(value, |bound| bound.min(value)));
+                continue;
+            }
+            if self.goal.is_reached(&puzzle_state) {
+                // cheaper solutions were yielded by an earlier iteration
+                if cost == self.bound {
+                    self.is_solution_found = true;
+                    let mut moves: Vec<Direction> = self.moves.clone();
+                    moves.push(direction);
+                    return Some(Solution { moves, cost });
+                }
+                continue;
+            }
+
+            self.moves.push(direction);
+            self.stack.push(Frame {
+                puzzle_state,
+                movable_piece_location: target,
+                next_move: 0,
+            });
+        }
+    }
+}
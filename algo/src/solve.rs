@@ -0,0 +1,132 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Direction, TOTAL_PUZZLE_SIZE};
+
+/// The puzzle state a solver ended in and the moves that lead there, see `schema` for its JSON.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Serialize, Deserialize)]
+pub struct SolvedState<const N: usize = TOTAL_PUZZLE_SIZE> {
+    #[serde(with = "crate::schema::puzzle_state")]
+    pub puzzle_state: [i8; N],
+    #[serde(with = "crate::schema::moves")]
+    pub moves: Vec<Direction>,
+    pub stats: SearchStats,
+}
+
+impl<const N: usize> SolvedState<N> {
+    pub fn new(puzzle_state: [i8; N], moves: Vec<Direction>) -> SolvedState<N> {
+        SolvedState {
+            puzzle_state,
+            moves,
+            stats: SearchStats::default(),
+        }
+    }
+}
+
+/// Counts the work a solver did.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[derive(Serialize, Deserialize)]
+pub struct SearchStats {
+    /// The number of nodes whose possible moves were generated.
+    pub expanded: u64,
+    /// The number of nodes that were created by a move.
+    pub generated: u64,
+    #[serde(rename = "elapsed_ms", with = "crate::schema::milliseconds")]
+    pub elapsed: Duration,
+}
+
+/// Bounds the work a solver may do before it gives up with `SolveError::LimitReached`.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Default)]
+pub struct SearchLimits {
+    pub max_expanded: Option<u64>,
+    pub time_limit: Option<Duration>,
+    /// Lets another thread stop the search.
+    pub cancel: Option<CancelFlag>,
+}
+
+/// A flag shared between threads that tells a solver to give up, it stops at its next expansion.
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Default)]
+pub struct CancelFlag(Arc<AtomicBool>);
+
+impl CancelFlag {
+    pub fn new() -> CancelFlag {
+        CancelFlag::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl PartialEq for CancelFlag {
+    /// Flags are equal when they are clones of each other.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl SearchLimits {
+    pub fn none() -> SearchLimits {
+        SearchLimits::default()
+    }
+
+    pub(crate) fn is_reached(&self, stats: &SearchStats, started: Instant) -> bool {
+        self.max_expanded.is_some_and(|max_expanded| stats.expanded >= max_expanded)
+            || self.time_limit.is_some_and(|time_limit| started.elapsed() >= time_limit)
+            || self.cancel.as_ref().is_some_and(CancelFlag::is_cancelled)
+    }
+}
+
+/// Reasons a solver can fail to produce a `SolvedState`.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum SolveError {
+    /// The goal cannot be reached from the given puzzle state.
+    Unsolvable,
+    /// The solver gave up because of its `SearchLimits`, with the work it did until then.
+    LimitReached(SearchStats),
+    /// The solver was given a checkpoint written for another puzzle state, goal or heuristic.
+    CheckpointMismatch,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Unsolvable => write!(f, "the goal cannot be reached from this puzzle state"),
+            SolveError::LimitReached(stats) => write!(f, "the search limit was reached after expanding {} nodes", stats.expanded),
+            SolveError::CheckpointMismatch => write!(f, "the checkpoint was written for another puzzle state, goal or heuristic"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+pub trait Solve<const N: usize = TOTAL_PUZZLE_SIZE> {
+    fn solve(&mut self, puzzle_state: &mut [i8; N]) -> Result<SolvedState<N>, SolveError>;
+}
+
+impl<const N: usize, S: Solve<N> + ?Sized> Solve<N> for Box<S> {
+    fn solve(&mut self, puzzle_state: &mut [i8; N]) -> Result<SolvedState<N>, SolveError> {
+        (**self).solve(puzzle_state)
+    }
+}
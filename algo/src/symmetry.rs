@@ -0,0 +1,86 @@
+use crate::{movable_piece, Direction, Goal, Topology, TOTAL_PUZZLE_SIZE, WILDCARD};
+
+/// The reflection of a square board about its main diagonal, together with the relabelling that
+/// maps the goal onto itself. A tile is relabelled to the tile whose goal position is the mirror
+/// of its own, so a puzzle state and its mirror need the same number of moves.
+///
+/// Only goals with the movable piece on the diagonal are mirrored onto themselves. A partial goal
+/// also needs its `WILDCARD` positions to mirror onto each other, and a board with blocked positions
+/// its blocked positions. A cylinder only wraps one way, so it has no symmetry.
+///
+/// # Examples
+///
+/// ```
+/// use algo::{Direction, Goal, Symmetry};
+///
+/// let goal: Goal = Goal::default();
+/// let symmetry = Symmetry::diagonal(&goal).unwrap();
+///
+/// assert_eq!(symmetry.mirror(goal.puzzle_state()), *goal.puzzle_state());
+/// // moving the movable piece left from the goal mirrors to moving it up
+/// assert_eq!(symmetry.mirror(&[0, 1, 2, 3, 4, 5, 6, 8, 7]), [0, 1, 2, 3, 4, 8, 6, 7, 5]);
+/// assert_eq!(Symmetry::<9>::mirror_direction(Direction::Left), Direction::Up);
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Symmetry<const N: usize = TOTAL_PUZZLE_SIZE> {
+    /// The position every position is reflected to.
+    positions: [usize; N],
+    /// The tile every tile is relabelled to.
+    tiles: [i8; N],
+}
+
+impl<const N: usize> Symmetry<N> {
+    /// Gets the reflection about the main diagonal, or `None` when the board isn't square
+    /// or the goal isn't mirrored onto itself.
+    pub fn diagonal(goal: &Goal<N>) -> Option<Symmetry<N>> {
+        let board = goal.board();
+        if board.width() != board.height() || board.topology() == Topology::Cylinder {
+            return None;
+        }
+        let positions: [usize; N] = core::array::from_fn(|position: usize| board.column(position) * board.width() + board.row(position));
+        if (0..N).any(|position| board.is_blocked(position) != board.is_blocked(positions[position])) {
+            return None;
+        }
+        let goal_state: &[i8; N] = goal.puzzle_state();
+        if (0..N).any(|position| (goal_state[position] == WILDCARD) != (goal_state[positions[position]] == WILDCARD)) {
+            return None;
+        }
+
+        let completed_state: [i8; N] = goal.completed_state();
+        let mut tiles: [i8; N] = [0; N];
+        for (position, tile) in completed_state.iter().enumerate() {
+            tiles[*tile as usize] = completed_state[positions[position]];
+        }
+        if tiles[movable_piece(N) as usize] != movable_piece(N) {
+            return None;
+        }
+        Some(Symmetry { positions, tiles })
+    }
+
+    /// Gets the position the given position is reflected to.
+    pub fn mirror_position(&self, position: usize) -> usize {
+        self.positions[position]
+    }
+
+    /// Reflects the puzzle state and relabels its tiles.
+    pub fn mirror(&self, puzzle_state: &[i8; N]) -> [i8; N] {
+        let mut mirrored_state: [i8; N] = [0; N];
+        for (position, tile) in puzzle_state.iter().enumerate() {
+            mirrored_state[self.positions[position]] = self.tiles[*tile as usize];
+        }
+        mirrored_state
+    }
+
+    /// Gets the move of the mirrored puzzle state that matches a move of the puzzle state.
+    pub fn mirror_direction(direction: Direction) -> Direction {
+        match direction {
+            Direction::Left => Direction::Up,
+            Direction::Right => Direction::Down,
+            Direction::Up => Direction::Left,
+            Direction::Down => Direction::Right,
+            Direction::None => Direction::None,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::{fmt, str::FromStr};
+
+/// Decides which of the open nodes with the same value a solver expands first.
+///
+/// The value of a node is its length plus its estimate, so between equal values a higher length is
+/// the same as a lower estimate. Both policies are kept so benchmarks can name the one they mean,
+/// and both fall back to the newest node when the lengths are equal too.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+pub enum TieBreaking {
+    /// The node furthest from the start, which tends to be the closest to the goal.
+    HigherLength,
+    /// The node with the lowest estimate.
+    LowerEstimate,
+    /// The node that was opened last.
+    #[default]
+    Lifo,
+    /// The node that was opened first.
+    Fifo,
+}
+
+impl TieBreaking {
+    pub const ALL: [TieBreaking; 4] = [TieBreaking::HigherLength, TieBreaking::LowerEstimate, TieBreaking::Lifo, TieBreaking::Fifo];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TieBreaking::HigherLength => "higher-length",
+            TieBreaking::LowerEstimate => "lower-estimate",
+            TieBreaking::Lifo => "lifo",
+            TieBreaking::Fifo => "fifo",
+        }
+    }
+
+    /// Gets the key that orders nodes with the same value, the highest key is expanded first.
+    /// The sequence counts up as nodes are opened.
+    pub(crate) fn key(&self, length: i32, estimate: i32, sequence: u64) -> (i64, i64) {
+        let sequence: i64 = sequence as i64;
+        match self {
+            TieBreaking::HigherLength => (length as i64, sequence),
+            TieBreaking::LowerEstimate => (-(estimate as i64), sequence),
+            TieBreaking::Lifo => (0, sequence),
+            TieBreaking::Fifo => (0, -sequence),
+        }
+    }
+}
+
+impl fmt::Display for TieBreaking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for TieBreaking {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<TieBreaking, String> {
+        TieBreaking::ALL
+            .into_iter()
+            .find(|tie_breaking| tie_breaking.name() == text)
+            .ok_or_else(|| format!("unknown tie-breaking '{}', expected one of higher-length, lower-estimate, lifo or fifo", text))
+    }
+}
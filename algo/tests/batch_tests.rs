@@ -0,0 +1,30 @@
+use algo::{AStar, BatchRunner, IdaStar, ScrambleMode, Scrambler, SearchLimits, Solve, SolveError};
+
+#[test]
+fn results_keep_the_input_order() {
+    let mut scrambler: Scrambler = Scrambler::new(99);
+    let puzzle_states: Vec<[i8; 9]> = (0..24).map(|_| scrambler.scramble(ScrambleMode::Shuffle).unwrap()).collect::<Vec<_>>();
+
+    let batch = BatchRunner::new(4).solve(&puzzle_states, IdaStar::new);
+
+    assert_eq!(batch.results.len(), puzzle_states.len());
+    assert_eq!(batch.workers, 4);
+    for (puzzle_state, result) in puzzle_states.iter().zip(batch.results.iter()) {
+        let expected_length: usize = AStar::new().solve(&mut puzzle_state.clone()).unwrap().moves.len();
+        assert_eq!(result.as_ref().unwrap().moves.len(), expected_length);
+    }
+    assert!(batch.throughput() > 0.0);
+}
+
+#[test]
+fn limits_apply_to_every_instance() {
+    let puzzle_states: Vec<[i8; 9]> = vec![[0, 1, 2, 3, 4, 5, 6, 8, 7], [7, 5, 6, 1, 4, 3, 2, 8, 0], [0, 1, 2, 3, 4, 5, 6, 8, 7]];
+    let limits: SearchLimits = SearchLimits { max_expanded: Some(100), ..SearchLimits::none() };
+
+    let batch = BatchRunner::new(8).solve(&puzzle_states, || AStar::new().with_limits(limits.clone()));
+
+    assert_eq!(batch.workers, 3);
+    assert!(batch.results[0].is_ok());
+    assert!(matches!(batch.results[1], Err(SolveError::LimitReached(_))));
+    assert!(batch.results[2].is_ok());
+}
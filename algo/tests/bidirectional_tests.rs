@@ -0,0 +1,61 @@
+use std::process::Command;
+
+use algo::{replay_moves, AStar, BidirectionalAStar, Board, Goal, Heuristic, ScrambleMode, Scrambler, Solve, SolveError};
+
+#[test]
+fn meets_in_the_middle_with_optimal_solutions() {
+    let board: Board = Board::square(4);
+    let goal: Goal<16> = Goal::blank_last(board);
+    let mut scrambler: Scrambler<16> = Scrambler::with_goal(37, goal.clone());
+
+    for _ in 0..5 {
+        let start: [i8; 16] = scrambler.scramble(ScrambleMode::RandomWalk(40)).unwrap();
+        let a_star = AStar::with_goal(goal.clone()).with_heuristic(Heuristic::LinearConflict).solve(&mut start.clone()).unwrap();
+        let bidirectional = BidirectionalAStar::with_goal(goal.clone()).with_heuristic(Heuristic::LinearConflict).solve(&mut start.clone()).unwrap();
+
+        assert_eq!(bidirectional.moves.len(), a_star.moves.len());
+        assert_eq!(&replay_moves(&board, &start, &bidirectional.moves).unwrap(), goal.puzzle_state());
+        assert!(bidirectional.stats.expanded > 0);
+    }
+}
+
+#[test]
+fn handles_trivial_partial_and_unreachable_goals() {
+    assert!(BidirectionalAStar::new().solve(&mut [0, 1, 2, 3, 4, 5, 6, 7, 8]).unwrap().moves.is_empty());
+    assert_eq!(BidirectionalAStar::new().solve(&mut [0, 1, 2, 3, 4, 5, 6, 8, 7]).unwrap().moves.len(), 1);
+    assert_eq!(BidirectionalAStar::new().solve(&mut [1, 0, 2, 3, 4, 5, 6, 7, 8]).err(), Some(SolveError::Unsolvable));
+
+    let goal: Goal = Goal::default().only_tiles(&[0, 1, 2]);
+    let mut start: [i8; 9] = [8, 7, 6, 5, 4, 3, 2, 1, 0];
+    let solved_state = BidirectionalAStar::with_goal(goal.clone()).solve(&mut start.clone()).unwrap();
+    assert_eq!(solved_state.moves.len(), AStar::with_goal(goal.clone()).solve(&mut start).unwrap().moves.len());
+}
+
+#[test]
+fn expands_about_as_many_nodes_as_a_star() {
+    let board: Board = Board::square(4);
+    let goal: Goal<16> = Goal::blank_last(board);
+    let mut scrambler: Scrambler<16> = Scrambler::with_goal(37, goal.clone());
+
+    let mut expanded: Vec<(u64, u64)> = Vec::new();
+    for _ in 0..5 {
+        let start: [i8; 16] = scrambler.scramble(ScrambleMode::RandomWalk(40)).unwrap();
+        let a_star = AStar::with_goal(goal.clone()).with_heuristic(Heuristic::LinearConflict).solve(&mut start.clone()).unwrap();
+        let bidirectional = BidirectionalAStar::with_goal(goal.clone()).with_heuristic(Heuristic::LinearConflict).solve(&mut start.clone()).unwrap();
+        expanded.push((a_star.stats.expanded, bidirectional.stats.expanded));
+    }
+
+    // searching from both ends never costs more than twice the nodes, and saves nodes over all instances
+    assert!(expanded.iter().all(|(a_star, bidirectional)| *bidirectional < 2 * *a_star), "{:?}", expanded);
+    assert!(expanded.iter().map(|(_, bidirectional)| bidirectional).sum::<u64>() < expanded.iter().map(|(a_star, _)| a_star).sum::<u64>(), "{:?}", expanded);
+}
+
+#[test]
+fn speedup_compares_the_expanded_nodes_with_a_star() {
+    let output = Command::new(env!("CARGO_BIN_EXE_algo")).args(["solve", "--solver", "bidirectional", "--speedup", "6", "3", "4", "2", "_", "5", "7", "1", "8"]).output().unwrap();
+    let text: String = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(text.lines().any(|line| line.starts_with("Sequential A*: Expanded ")), "{}", text);
+    assert!(text.lines().any(|line| line.starts_with("Expanded ") && line.contains(" of sequential A*, ")), "{}", text);
+}
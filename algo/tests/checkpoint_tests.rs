@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use algo::{Board, Checkpoint, Goal, Heuristic, IdaStar, PatternDatabase, SearchLimits, Solve, SolveError, SolvedState};
+
+fn checkpoint_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("algo-{}-{}.checkpoint", name, std::process::id()))
+}
+
+fn limits(max_expanded: u64) -> SearchLimits {
+    SearchLimits { max_expanded: Some(max_expanded), ..SearchLimits::none() }
+}
+
+#[test]
+fn resumes_to_the_same_solved_state() {
+    let start: [i8; 9] = [7, 5, 6, 1, 4, 3, 2, 8, 0];
+    let expected: SolvedState = IdaStar::new().with_heuristic(Heuristic::LinearConflict).solve(&mut start.clone()).unwrap();
+    let path: PathBuf = checkpoint_path("resume");
+
+    for max_expanded in [1, 2, 50, 700, expected.stats.expanded - 1] {
+        let mut solver: IdaStar = IdaStar::new().with_heuristic(Heuristic::LinearConflict).with_limits(limits(max_expanded)).with_checkpoints(&path, Duration::from_secs(3600));
+        assert!(matches!(solver.solve(&mut start.clone()), Err(SolveError::LimitReached(stats)) if stats.expanded == max_expanded));
+
+        let checkpoint: Checkpoint = Checkpoint::read(&path).unwrap();
+        assert_eq!(checkpoint.stats.expanded, max_expanded);
+        let solved_state: SolvedState = IdaStar::new().with_heuristic(Heuristic::LinearConflict).with_resume(checkpoint).solve(&mut start.clone()).unwrap();
+
+        assert_eq!(solved_state.moves, expected.moves, "stopped after {} nodes", max_expanded);
+        assert_eq!(solved_state.puzzle_state, expected.puzzle_state);
+        assert_eq!((solved_state.stats.expanded, solved_state.stats.generated), (expected.stats.expanded, expected.stats.generated));
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn resumes_again_after_every_stop() {
+    let start: [i8; 8] = [5, 0, 2, 7, 4, 1, 6, 3];
+    let goal: Goal<8> = Goal::blank_last(Board::new(4, 2));
+    let expected: SolvedState<8> = IdaStar::with_goal(goal.clone()).solve(&mut start.clone()).unwrap();
+    let path: PathBuf = checkpoint_path("stops");
+
+    let mut checkpoint: Option<Checkpoint<8>> = None;
+    let mut max_expanded: u64 = 0;
+    let solved_state: SolvedState<8> = loop {
+        max_expanded += 25;
+        let mut solver: IdaStar<8> = IdaStar::with_goal(goal.clone()).with_limits(limits(max_expanded)).with_checkpoints(&path, Duration::from_secs(3600));
+        if let Some(checkpoint) = checkpoint.take() {
+            solver = solver.with_resume(checkpoint);
+        }
+        match solver.solve(&mut start.clone()) {
+            Ok(solved_state) => break solved_state,
+            Err(_) => checkpoint = Some(Checkpoint::read(&path).unwrap()),
+        }
+    };
+
+    assert!(max_expanded > 25);
+    assert_eq!(solved_state.moves, expected.moves);
+    assert_eq!((solved_state.stats.expanded, solved_state.stats.generated), (expected.stats.expanded, expected.stats.generated));
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn writes_checkpoints_as_json() {
+    let start: [i8; 9] = [8, 1, 2, 0, 4, 3, 7, 6, 5];
+    let path: PathBuf = checkpoint_path("json");
+
+    let mut solver: IdaStar = IdaStar::new().with_limits(limits(3)).with_checkpoints(&path, Duration::ZERO);
+    assert!(solver.solve(&mut start.clone()).is_err());
+    assert!(solver.checkpoint_error().is_none());
+
+    let text: String = std::fs::read_to_string(&path).unwrap();
+    let checkpoint: Checkpoint = serde_json::from_str(&text).unwrap();
+    assert_eq!(checkpoint.puzzle_state, start);
+    assert_eq!(checkpoint.heuristic, Heuristic::Manhattan);
+    assert_eq!((checkpoint.board, checkpoint.goal), (Board::default(), *Goal::default().puzzle_state()));
+    assert!(text.starts_with("{\"puzzle_state\":[0,2,3,1,5,4,8,7,6],\"board\":{\"width\":3,\"height\":3},\"goal\":[1,2,3,4,5,6,7,8,0],\"heuristic\":\"manhattan\",\"bound\":"), "{}", text);
+    assert_eq!(serde_json::from_str::<Checkpoint>(&serde_json::to_string(&checkpoint).unwrap()).unwrap(), checkpoint);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn refuses_checkpoints_of_another_search() {
+    let start: [i8; 9] = [7, 5, 6, 1, 4, 3, 2, 8, 0];
+    let path: PathBuf = checkpoint_path("mismatch");
+    let mut solver: IdaStar = IdaStar::new().with_limits(limits(100)).with_checkpoints(&path, Duration::from_secs(3600));
+    assert!(solver.solve(&mut start.clone()).is_err());
+    let checkpoint: Checkpoint = Checkpoint::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let other_start: [i8; 9] = [0, 1, 2, 3, 4, 5, 6, 8, 7];
+    assert_eq!(IdaStar::new().with_resume(checkpoint.clone()).solve(&mut other_start.clone()), Err(SolveError::CheckpointMismatch));
+    assert_eq!(IdaStar::new().with_heuristic(Heuristic::LinearConflict).with_resume(checkpoint.clone()).solve(&mut start.clone()), Err(SolveError::CheckpointMismatch));
+    let partial_goal: Goal = Goal::default().only_tiles(&[0, 1, 2]);
+    assert_eq!(IdaStar::with_goal(partial_goal.clone()).with_resume(checkpoint.clone()).solve(&mut start.clone()), Err(SolveError::CheckpointMismatch));
+    let pattern_database: Arc<PatternDatabase> = Arc::new(PatternDatabase::new(&Goal::default(), &[0, 1, 2]).unwrap());
+    assert_eq!(IdaStar::new().with_pattern_database(pattern_database).with_resume(checkpoint.clone()).solve(&mut start.clone()), Err(SolveError::CheckpointMismatch));
+    assert!(IdaStar::new().with_resume(checkpoint.clone()).solve(&mut start.clone()).is_ok());
+
+    // the positions a partial goal leaves open are written as null
+    let partial_checkpoint: Checkpoint = Checkpoint { goal: *partial_goal.puzzle_state(), ..checkpoint };
+    let text: String = serde_json::to_string(&partial_checkpoint).unwrap();
+    assert!(text.contains("\"goal\":[1,2,3,null,null,null,null,null,null]"), "{}", text);
+    assert_eq!(serde_json::from_str::<Checkpoint>(&text).unwrap(), partial_checkpoint);
+}
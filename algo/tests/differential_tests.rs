@@ -0,0 +1,81 @@
+use algo::{format_instances, parse_instances, AStar, BatchRunner, BidirectionalAStar, DifferentialReport, DifferentialTester, Discrepancy, Goal, IdaStar, ScrambleMode, Solve, SolveError, SolvedState};
+
+/// Solves with A* and then spoils the moves.
+struct Spoiled {
+    spoil: fn(&mut SolvedState),
+}
+
+impl Solve for Spoiled {
+    fn solve(&mut self, puzzle_state: &mut [i8; 9]) -> Result<SolvedState, SolveError> {
+        let mut solved_state: SolvedState = AStar::new().solve(puzzle_state)?;
+        (self.spoil)(&mut solved_state);
+        Ok(solved_state)
+    }
+}
+
+/// Steps back and forth before the moves, which still solves the puzzle in two more moves.
+fn detour(solved_state: &mut SolvedState) {
+    let first_move = solved_state.moves[0];
+    solved_state.moves.splice(0..0, [first_move, first_move.opposite()]);
+}
+
+/// Leaves out the last move, so the moves stop one short of the goal.
+fn truncate(solved_state: &mut SolvedState) {
+    solved_state.moves.pop();
+}
+
+#[test]
+fn optimal_solvers_agree() {
+    let report: DifferentialReport = DifferentialTester::new(7)
+        .with_instances(10)
+        .with_runner(BatchRunner::new(2))
+        .with_solver("astar", AStar::new)
+        .with_solver("idastar", IdaStar::new)
+        .with_solver("bidirectional", BidirectionalAStar::new)
+        .run()
+        .unwrap();
+
+    assert!(report.is_ok());
+    assert_eq!(report.checks.len(), 10);
+    for check in &report.checks {
+        assert_eq!(check.lengths.len(), 3);
+        assert!(check.lengths.iter().all(|(_, length)| Some(*length) == check.shortest_moves.as_ref().map(Vec::len)));
+    }
+}
+
+#[test]
+fn longer_moves_of_an_optimal_solver_shrink_to_a_single_move() {
+    let tester = || DifferentialTester::new(3).with_instances(4).with_scramble_mode(ScrambleMode::RandomWalk(16)).with_solver("astar", AStar::new);
+
+    let report: DifferentialReport = tester().with_solver("detour", || Spoiled { spoil: detour }).run().unwrap();
+    assert_eq!(report.failures.len(), 4);
+    for failure in &report.failures {
+        assert!(matches!(&report.checks[failure.index].discrepancies[..], [Discrepancy::LengthMismatch { .. }]));
+        // every puzzle state one move from the goal already shows the detour
+        assert_eq!(failure.minimal.shortest_moves.as_ref().map(Vec::len), Some(1));
+        assert_eq!(failure.minimal.lengths, vec![("astar".to_string(), 1), ("detour".to_string(), 3)]);
+    }
+
+    // solvers that don't claim to be optimal may take detours
+    assert!(tester().with_inexact_solver("detour", || Spoiled { spoil: detour }).run().unwrap().is_ok());
+}
+
+#[test]
+fn invalid_moves_are_reported_with_a_reproducing_instance() {
+    let report: DifferentialReport = DifferentialTester::new(11)
+        .with_instances(2)
+        .with_solver("astar", AStar::new)
+        .with_inexact_solver("truncated", || Spoiled { spoil: truncate })
+        .run()
+        .unwrap();
+
+    assert_eq!(report.failures.len(), 2);
+    let failure = &report.failures[0];
+    assert!(matches!(&failure.minimal.discrepancies[..], [Discrepancy::InvalidMoves { solver, .. }] if solver == "truncated"));
+    assert_eq!(failure.minimal.lengths.len(), 1);
+
+    let instances = parse_instances(&format_instances(&report.reproducing_instances(&Goal::default()))).unwrap();
+    assert_eq!(instances.len(), 2);
+    assert_eq!(instances[0].name.as_deref(), Some("seed-11-1"));
+    assert_eq!(instances[0].puzzle_state::<9>(), Some(failure.minimal.puzzle_state));
+}
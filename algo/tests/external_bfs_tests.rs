@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use algo::{BfsReport, Board, DistanceTable, ExternalBfs, ExternalBfsError, Goal, WILDCARD};
+
+fn directory(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("algo-bfs-{}-{}", name, std::process::id()))
+}
+
+#[test]
+fn reproduces_the_eight_puzzle_diameter() {
+    let goal: Goal = Goal::default();
+    let directory: PathBuf = directory("eight");
+    let report: BfsReport = ExternalBfs::new(goal.clone(), &directory).with_buffer_states(5000).run().unwrap();
+
+    assert_eq!(report.total(), 181_440);
+    assert_eq!(report.max_depth(), 31);
+    assert_eq!(report.layer_sizes[..4], [1, 2, 4, 8]);
+    assert_eq!(report.layer_sizes[31], 2);
+    assert_eq!(report.antipodes.len(), 2);
+    let distance_table: DistanceTable = DistanceTable::new(&goal).unwrap();
+    for (depth, size) in report.layer_sizes.iter().enumerate() {
+        assert_eq!(distance_table.states_at(depth).count() as u64, *size, "layer {}", depth);
+    }
+    for antipode in &report.antipodes {
+        assert_eq!(distance_table.distance(antipode), Some(31));
+    }
+    assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 0);
+    std::fs::remove_dir(&directory).unwrap();
+}
+
+#[test]
+fn searches_abstractions_and_keeps_layers() {
+    let goal: Goal<8> = Goal::blank_last(Board::new(4, 2)).only_tiles(&[0, 1, 2, 7]);
+    let directory: PathBuf = directory("abstraction");
+    let bfs: ExternalBfs<8> = ExternalBfs::new(goal.clone(), &directory).with_buffer_states(64).with_examples(3).with_keep_layers(true);
+    let report: BfsReport<8> = bfs.run().unwrap();
+
+    // the movable piece and three tiles among the four tiles that can't be told apart
+    assert_eq!(report.total(), 8 * 7 * 6 * 5);
+    assert_eq!(report.max_depth(), DistanceTable::new(&goal).unwrap().max_distance());
+    assert_eq!(report.antipodes.len(), 3.min(*report.layer_sizes.last().unwrap() as usize));
+    assert!(report.antipodes.iter().all(|antipode| antipode.iter().filter(|tile| **tile == WILDCARD).count() == 4));
+    for (depth, size) in report.layer_sizes.iter().enumerate() {
+        // five bytes a state, 8 positions of 5 bits
+        assert_eq!(std::fs::metadata(bfs.layer_path(depth)).unwrap().len(), size * 5);
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn needs_the_movable_piece_in_the_goal() {
+    let goal: Goal = Goal::default().only_tiles(&[0, 1]);
+
+    assert!(matches!(ExternalBfs::new(goal, directory("no-blank")).run(), Err(ExternalBfsError::NoMovablePiece)));
+}
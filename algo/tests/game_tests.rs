@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use algo::{AStar, Board, Direction, Game, Goal, IdaStar, SavedGame, SavedGameError, Solve};
+
+#[test]
+fn undoes_and_redoes_moves() {
+    let mut game: Game = Game::new(Goal::default(), [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+    assert!(!game.play(Direction::Right));
+    assert!(game.play(Direction::Up));
+    assert!(game.slide(Direction::Right));
+    assert_eq!(game.moves(), [Direction::Up, Direction::Left]);
+    assert_eq!(*game.puzzle_state(), [0, 1, 2, 3, 8, 4, 6, 7, 5]);
+
+    assert_eq!(game.undo(), Some(Direction::Left));
+    assert_eq!(game.undo(), Some(Direction::Up));
+    assert_eq!(game.undo(), None);
+    assert_eq!(game.puzzle_state(), game.start());
+    assert_eq!(game.redo(), Some(Direction::Up));
+
+    // a new move forgets the moves that were left to redo
+    assert!(game.play(Direction::Down));
+    assert_eq!(game.redo(), None);
+    assert!(game.is_solved());
+}
+
+#[test]
+fn hints_follow_a_shortest_solution() {
+    let start: [i8; 9] = [7, 5, 6, 1, 4, 3, 2, 8, 0];
+    let length: usize = AStar::new().solve(&mut start.clone()).unwrap().moves.len();
+    let mut game: Game = Game::new(Goal::default(), start);
+
+    while let Some(direction) = game.hint(&mut IdaStar::new()).unwrap() {
+        assert!(game.play(direction));
+    }
+    assert!(game.is_solved());
+    assert_eq!(game.moves().len(), length);
+}
+
+#[test]
+fn continues_saved_games() {
+    let goal: Goal<8> = Goal::blank_first(Board::new(4, 2));
+    let mut game: Game<8> = Game::new(goal, [7, 0, 1, 2, 3, 4, 5, 6]);
+    for direction in [Direction::Right, Direction::Down, Direction::Left, Direction::Up] {
+        assert!(game.play(direction));
+    }
+    game.undo();
+    game.undo();
+
+    let path: PathBuf = std::env::temp_dir().join(format!("algo-game-{}.json", std::process::id()));
+    game.saved().write(&path).unwrap();
+    let text: String = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(text, r#"{"board":{"width":4,"height":2},"goal":[0,1,2,3,4,5,6,7],"start":[0,1,2,3,4,5,6,7],"moves":"RD","undone":"UL"}"#);
+    let mut loaded_game: Game<8> = Game::from_saved(&SavedGame::read(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded_game, game);
+    assert_eq!(loaded_game.redo(), Some(Direction::Left));
+
+    let mut saved_game: SavedGame<8> = game.saved();
+    saved_game.undone.push(Direction::Up);
+    assert!(matches!(Game::from_saved(&saved_game), Err(SavedGameError::IllegalMove(4))));
+}
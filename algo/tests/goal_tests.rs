@@ -0,0 +1,79 @@
+use algo::{replay_moves, AStar, Board, Direction, Goal, Node, Solve, SolveError, WILDCARD};
+
+#[test]
+fn goal_layouts_place_the_movable_piece() {
+    assert_eq!(Goal::blank_first(Board::square(3)).puzzle_state(), &[8, 0, 1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(Goal::spiral(Board::square(3)).puzzle_state(), &[0, 1, 2, 7, 8, 3, 6, 5, 4]);
+    assert_eq!(Goal::snake(Board::square(3)).puzzle_state(), &[0, 1, 2, 5, 4, 3, 6, 7, 8]);
+}
+
+#[test]
+fn goal_rejects_invalid_permutations() {
+    assert!(Goal::new(Board::square(3), [0, 0, 1, 2, 3, 4, 5, 6, 8]).is_err());
+    assert!(Goal::new(Board::square(3), [0, 1, 2, 3, 4, 5, 6, 7, 9]).is_err());
+}
+
+#[test]
+fn a_star_solves_towards_spiral_goal() {
+    let goal: Goal = Goal::spiral(Board::square(3));
+    let start: [i8; 9] = replay_moves(&Board::default(), goal.puzzle_state(), &[Direction::Up, Direction::Left, Direction::Down, Direction::Right, Direction::Down]).unwrap();
+
+    let solved_state = AStar::with_goal(goal.clone()).solve(&mut start.clone()).unwrap();
+
+    assert_eq!(&solved_state.puzzle_state, goal.puzzle_state());
+    assert_eq!(&replay_moves(&Board::default(), &start, &solved_state.moves).unwrap(), goal.puzzle_state());
+}
+
+#[test]
+fn a_star_solves_between_two_states() {
+    let start: [i8; 9] = [5, 2, 3, 1, 8, 4, 6, 0, 7];
+    let end: [i8; 9] = replay_moves(&Board::default(), &start, &[Direction::Left, Direction::Down, Direction::Right, Direction::Right]).unwrap();
+
+    let solved_state = AStar::with_goal(Goal::new(Board::square(3), end).unwrap()).solve(&mut start.clone()).unwrap();
+
+    assert_eq!(solved_state.moves.len(), 4);
+    assert_eq!(replay_moves(&Board::default(), &start, &solved_state.moves).unwrap(), end);
+}
+
+#[test]
+fn a_star_reports_unreachable_goals() {
+    let mut start: [i8; 9] = [1, 0, 2, 3, 4, 5, 6, 7, 8];
+
+    assert!(matches!(AStar::new().solve(&mut start), Err(SolveError::Unsolvable)));
+}
+
+#[test]
+fn a_star_stops_once_partial_goal_is_in_place() {
+    let goal: Goal = Goal::default().only_positions(|position| position < 3 || position % 3 == 0);
+    let mut start: [i8; 9] = [5, 2, 3, 1, 8, 4, 6, 0, 7];
+
+    let solved_state = AStar::with_goal(goal.clone()).solve(&mut start).unwrap();
+
+    assert!(goal.is_reached(&solved_state.puzzle_state));
+    assert_eq!(&solved_state.puzzle_state[..3], &[0, 1, 2]);
+    assert_eq!(solved_state.puzzle_state[3], 3);
+    assert_eq!(solved_state.puzzle_state[6], 6);
+}
+
+#[test]
+fn partial_goal_heuristic_only_counts_constrained_tiles() {
+    let goal: Goal = Goal::partial(Board::square(3), [0, 1, WILDCARD, WILDCARD, WILDCARD, WILDCARD, WILDCARD, WILDCARD, WILDCARD]).unwrap();
+
+    assert_eq!(Node::manhattan_distance(&[8, 7, 6, 5, 4, 3, 2, 1, 0], &goal), 6);
+    assert!(goal.is_reachable_from(&[1, 0, 2, 3, 4, 5, 6, 7, 8]));
+}
+
+#[test]
+fn partial_goals_need_the_tiles_the_movable_piece_cant_get_to_in_place() {
+    // positions 1 and 4 wall off the top left corner
+    let board: Board = Board::square(4).with_blocked(&[1, 4]);
+    let mut goal_state: [i8; 16] = [WILDCARD; 16];
+    goal_state[0] = 0;
+    let goal: Goal<16> = Goal::partial(board, goal_state).unwrap();
+
+    let mut puzzle_state: [i8; 16] = std::array::from_fn(|position| position as i8);
+    assert!(goal.is_reachable_from(&puzzle_state));
+    puzzle_state.swap(0, 5);
+    assert!(!goal.is_reachable_from(&puzzle_state));
+    assert_eq!(AStar::with_goal(goal).solve(&mut puzzle_state).err(), Some(SolveError::Unsolvable));
+}
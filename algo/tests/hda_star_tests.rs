@@ -0,0 +1,26 @@
+use algo::{replay_moves, AStar, Board, Goal, HdaStar, Heuristic, ScrambleMode, Scrambler, SearchLimits, Solve, SolveError};
+
+#[test]
+fn finds_optimal_solutions_on_any_number_of_threads() {
+    let mut scrambler: Scrambler = Scrambler::new(35);
+    for _ in 0..5 {
+        let start: [i8; 9] = scrambler.scramble(ScrambleMode::Shuffle).unwrap();
+        let optimal_length: usize = AStar::new().solve(&mut start.clone()).unwrap().moves.len();
+
+        for threads in [1, 2, 3] {
+            let solved_state = HdaStar::new().with_heuristic(Heuristic::LinearConflict).with_threads(threads).solve(&mut start.clone()).unwrap();
+
+            assert_eq!(solved_state.moves.len(), optimal_length);
+            assert!(Goal::<9>::default().is_reached(&replay_moves(&Board::default(), &start, &solved_state.moves).unwrap()));
+        }
+    }
+}
+
+#[test]
+fn stops_at_limits_and_unreachable_goals() {
+    let mut start: [i8; 9] = [7, 5, 6, 1, 4, 3, 2, 8, 0];
+    let limits: SearchLimits = SearchLimits { max_expanded: Some(50), ..SearchLimits::none() };
+
+    assert!(matches!(HdaStar::new().with_threads(2).with_limits(limits).solve(&mut start), Err(SolveError::LimitReached(_))));
+    assert_eq!(HdaStar::new().with_threads(2).solve(&mut [1, 0, 2, 3, 4, 5, 6, 7, 8]).err(), Some(SolveError::Unsolvable));
+}
@@ -0,0 +1,49 @@
+use algo::{effective_branching_factor, BatchRunner, Heuristic, HeuristicAnalysis, HeuristicQuality, ScrambleMode, Scrambler, SearchLimits};
+
+fn scrambled(count: usize) -> Vec<[i8; 9]> {
+    let mut scrambler: Scrambler = Scrambler::new(5);
+    (0..count).map(|_| scrambler.scramble(ScrambleMode::ExactDistance(12)).unwrap()).collect::<Vec<_>>()
+}
+
+#[test]
+fn effective_branching_factor_fills_a_uniform_tree() {
+    assert!((effective_branching_factor(3, 1) - 3.0).abs() < 1e-9);
+    assert!((effective_branching_factor(14, 3) - 2.0).abs() < 1e-9);
+    assert!((effective_branching_factor(3, 3) - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn estimates_are_compared_with_the_optimal_distance() {
+    let qualities: Vec<HeuristicQuality> = HeuristicAnalysis::new().with_runner(BatchRunner::new(2)).analyse(&scrambled(6));
+
+    assert_eq!(qualities.iter().map(|quality| quality.heuristic).collect::<Vec<_>>(), Heuristic::ALL.to_vec());
+    for quality in &qualities {
+        assert_eq!(quality.mean_distance(), Some(12.0));
+        assert_eq!(quality.gave_up(), 0);
+        // none of the heuristics overestimates
+        let distribution = quality.shortfall_distribution();
+        assert_eq!(distribution.values().sum::<usize>(), 6);
+        assert!(distribution.keys().all(|shortfall| *shortfall >= 0));
+        assert!(quality.mean_ratio().unwrap() <= 1.0);
+        assert!(quality.mean_effective_branching_factor().unwrap() >= 1.0);
+    }
+
+    let (manhattan, misplaced_tiles, linear_conflict) = (&qualities[0], &qualities[1], &qualities[2]);
+    for index in 0..6 {
+        assert!(misplaced_tiles.instances[index].estimate <= manhattan.instances[index].estimate);
+        assert!(manhattan.instances[index].estimate <= linear_conflict.instances[index].estimate);
+    }
+    assert!(misplaced_tiles.mean_expanded() > linear_conflict.mean_expanded());
+}
+
+#[test]
+fn searches_that_give_up_are_counted() {
+    let limits: SearchLimits = SearchLimits { max_expanded: Some(1), ..SearchLimits::none() };
+    let qualities: Vec<HeuristicQuality> = HeuristicAnalysis::new().with_heuristics(&[Heuristic::Manhattan]).with_limits(limits).analyse(&scrambled(2));
+
+    assert_eq!(qualities[0].gave_up(), 2);
+    assert_eq!(qualities[0].instances[0].distance, None);
+    assert_eq!(qualities[0].mean_expanded(), None);
+    assert!(qualities[0].shortfall_distribution().is_empty());
+    assert!(qualities[0].mean_estimate().is_some());
+}
@@ -0,0 +1,63 @@
+use algo::{Board, Goal, Image, ImageError, ImagePuzzle, ScrambleMode, Scrambler};
+
+/// A picture where every pixel has its own colour.
+fn gradient(width: usize, height: usize) -> Image {
+    let mut image: Image = Image::new(width, height, [0, 0, 0]);
+    for y in 0..height {
+        for x in 0..width {
+            image.set_pixel(x, y, [x as u8 * 10, y as u8 * 10, 200]);
+        }
+    }
+    image
+}
+
+#[test]
+fn renders_tiles_from_their_goal_positions() {
+    // 7x5 pixels on a 3x2 board leaves a column and a row out of 2x2 tiles
+    let goal: Goal<6> = Goal::spiral(Board::new(3, 2));
+    let image_puzzle: ImagePuzzle<6> = ImagePuzzle::new(gradient(7, 5), goal.clone()).unwrap().with_blank_colour([1, 2, 3]);
+    assert_eq!(image_puzzle.tile_size(), (2, 2));
+
+    let completed: Image = image_puzzle.render(goal.puzzle_state());
+    let blank_position: usize = goal.position_of(5).unwrap();
+    for (x, y) in (0..4).flat_map(|y| (0..6).map(move |x| (x, y))) {
+        let position: usize = y / 2 * 3 + x / 2;
+        let expected: [u8; 3] = if position == blank_position { [1, 2, 3] } else { [x as u8 * 10, y as u8 * 10, 200] };
+        assert_eq!(completed.pixel(x, y), expected, "pixel {},{}", x, y);
+    }
+
+    let puzzle_state: [i8; 6] = Scrambler::with_goal(3, goal.clone()).scramble(ScrambleMode::Shuffle).unwrap();
+    let rendered: Image = image_puzzle.render(&puzzle_state);
+    assert_eq!(rendered, image_puzzle.render(&puzzle_state));
+    for (position, tile) in puzzle_state.iter().enumerate().filter(|(_, tile)| **tile != 5) {
+        let home_position: usize = goal.position_of(*tile).unwrap();
+        let (x, y) = (position % 3 * 2 + 1, position / 3 * 2 + 1);
+        assert_eq!(rendered.pixel(x, y), completed.pixel(home_position % 3 * 2 + 1, home_position / 3 * 2 + 1));
+    }
+}
+
+#[test]
+fn reads_and_writes_png_and_ppm() {
+    let image: Image = gradient(5, 3);
+    assert_eq!(Image::decode(&image.to_png().unwrap()).unwrap(), image);
+    assert_eq!(Image::decode(&image.to_ppm()).unwrap(), image);
+
+    let path = std::env::temp_dir().join(format!("algo-image-{}.png", std::process::id()));
+    image.write(&path).unwrap();
+    assert!(std::fs::read(&path).unwrap().starts_with(b"\x89PNG"));
+    assert_eq!(Image::read(&path).unwrap(), image);
+    std::fs::remove_file(&path).unwrap();
+
+    // comments in the header and samples of fewer bits are scaled to 8 bits
+    let small: Image = Image::decode(b"P6\n# a comment\n2 1\n15\n\x0f\x00\x05\x00\x0f\x0f").unwrap();
+    assert_eq!((small.pixel(0, 0), small.pixel(1, 0)), ([255, 0, 85], [0, 255, 255]));
+}
+
+#[test]
+fn rejects_invalid_images() {
+    assert!(matches!(Image::decode(b"P3\n1 1\n255\n0 0 0"), Err(ImageError::InvalidPpm(_))));
+    assert!(matches!(Image::decode(b"P6\n2 2\n255\n\x00\x00\x00"), Err(ImageError::InvalidPpm(_))));
+    assert!(matches!(Image::decode(b"P6 9999999999 9999999999 255\n"), Err(ImageError::InvalidPpm(reason)) if reason == "the image is too large"));
+    assert!(matches!(Image::decode(b"\x89PNG\r\n\x1a\nbroken"), Err(ImageError::InvalidPng(_))));
+    assert!(matches!(ImagePuzzle::<9>::new(gradient(2, 5), Goal::default()), Err(ImageError::TooSmall(2, 5))));
+}
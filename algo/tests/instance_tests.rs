@@ -0,0 +1,33 @@
+use algo::{format_instances, parse_instances, Board, Instance, ParseInstanceError, ParseInstanceErrorKind, ParseStateError};
+
+#[test]
+fn reads_every_instance_of_a_file() {
+    let instances: Vec<Instance> = parse_instances(include_str!("../instances/examples.txt")).unwrap();
+
+    assert_eq!(instances.len(), 8);
+    assert_eq!(instances[3].name.as_deref(), Some("15-puzzle-1"));
+    assert_eq!(instances[3].board, Board::square(4));
+    assert_eq!(instances[6].board, Board::new(3, 2));
+    assert_eq!(instances[6].puzzle_state::<6>(), Some([1, 4, 5, 2, 3, 0]));
+    assert_eq!(instances[6].puzzle_state::<9>(), None);
+}
+
+#[test]
+fn written_instances_read_back_the_same() {
+    let instances: Vec<Instance> = vec![
+        Instance::new(None, Board::square(3), &[5, 2, 3, 1, 8, 4, 6, 0, 7]),
+        Instance::new(Some("wide one".to_string()), Board::new(4, 2), &[4, 0, 1, 3, 7, 5, 2, 6]),
+    ];
+
+    assert_eq!(parse_instances(&format_instances(&instances)).unwrap(), instances);
+}
+
+#[test]
+fn errors_point_at_the_line() {
+    let error = |line: usize, kind: ParseInstanceErrorKind| Err(ParseInstanceError { line, kind });
+
+    assert_eq!(parse_instances("2x2\n1 2\n3 _\n\n3x3\n1 2 3\n4 5\n"), error(7, ParseInstanceErrorKind::WrongRowLength(2, 3)));
+    assert_eq!(parse_instances("# comment\n2x2\n1 2\n"), error(2, ParseInstanceErrorKind::MissingRows));
+    assert_eq!(parse_instances("2x2\n1 2\n2 _\n"), error(1, ParseInstanceErrorKind::InvalidState(ParseStateError::DuplicateTile("2".to_string()))));
+    assert!(matches!(parse_instances("2by2\n1 2\n3 _\n"), Err(ParseInstanceError { line: 1, kind: ParseInstanceErrorKind::InvalidBoard(_) })));
+}
@@ -0,0 +1,100 @@
+use std::collections::{HashMap, VecDeque};
+
+use algo::{parse_labels, Board, Direction, LabelledAStar, LabelledGoal, LabelledGoalError, LabelledMove, ParseStateError};
+
+/// Gets the distance of every state reachable from the goal, every move can be undone by another move.
+fn distances<const N: usize>(goal: &LabelledGoal<N>) -> HashMap<[i8; N], usize> {
+    let mut distances: HashMap<[i8; N], usize> = HashMap::from([(*goal.labels(), 0)]);
+    let mut queue: VecDeque<[i8; N]> = VecDeque::from([*goal.labels()]);
+    while let Some(labels) = queue.pop_front() {
+        let distance: usize = distances[&labels];
+        for labelled_move in goal.moves(&labels) {
+            let mut next_labels: [i8; N] = labels;
+            goal.apply(&mut next_labels, labelled_move).unwrap();
+            distances.entry(next_labels).or_insert_with(|| {
+                queue.push_back(next_labels);
+                distance + 1
+            });
+        }
+    }
+    distances
+}
+
+/// Gets every arrangement of the labels.
+fn arrangements<const N: usize>(labels: &[i8; N]) -> Vec<[i8; N]> {
+    let mut sorted: [i8; N] = *labels;
+    sorted.sort();
+    let mut arrangements: Vec<[i8; N]> = vec![sorted];
+    // the next permutation in lexicographic order, which skips the repeated ones
+    loop {
+        let mut next: [i8; N] = *arrangements.last().unwrap();
+        let Some(i) = (0..N - 1).rev().find(|i| next[*i] < next[i + 1]) else {
+            return arrangements;
+        };
+        let j: usize = (i + 1..N).rev().find(|j| next[*j] > next[i]).unwrap();
+        next.swap(i, j);
+        next[i + 1..].reverse();
+        arrangements.push(next);
+    }
+}
+
+#[test]
+fn solves_two_blanks_optimally() {
+    let goal: LabelledGoal = LabelledGoal::new(Board::square(3), [1, 1, 1, 2, 2, 2, 3, 0, 0]).unwrap();
+    let distances: HashMap<[i8; 9], usize> = distances(&goal);
+    let arrangements: Vec<[i8; 9]> = arrangements(goal.labels());
+
+    assert_eq!(arrangements.len(), 5040);
+    assert_eq!(distances.len(), arrangements.len());
+    for labels in &arrangements {
+        assert!(goal.is_reachable_from(labels));
+        assert!(goal.estimate(labels) as usize <= distances[labels], "{:?}", labels);
+    }
+    for labels in arrangements.iter().step_by(97) {
+        let solution = LabelledAStar::with_goal(goal.clone()).solve(labels).unwrap();
+        assert_eq!(solution.moves.len(), distances[labels], "{:?}", labels);
+
+        let mut replayed: [i8; 9] = *labels;
+        for labelled_move in &solution.moves {
+            goal.apply(&mut replayed, *labelled_move).unwrap();
+        }
+        assert_eq!(replayed, solution.labels);
+        assert!(goal.is_reached(&replayed));
+    }
+}
+
+#[test]
+fn checks_reachability_with_a_single_blank() {
+    let board: Board = Board::new(3, 2);
+    let distinct: LabelledGoal<6> = LabelledGoal::new(board, [1, 2, 3, 4, 5, 0]).unwrap();
+    let distinct_distances: HashMap<[i8; 6], usize> = distances(&distinct);
+    assert_eq!(distinct_distances.len(), 360);
+    for labels in arrangements(distinct.labels()) {
+        assert_eq!(distinct.is_reachable_from(&labels), distinct_distances.contains_key(&labels), "{:?}", labels);
+    }
+
+    let shared: LabelledGoal<6> = LabelledGoal::new(board, [1, 1, 2, 3, 4, 0]).unwrap();
+    assert_eq!(distances(&shared).len(), 360);
+    assert!(arrangements(shared.labels()).iter().all(|labels| shared.is_reachable_from(labels)));
+    assert!(!shared.is_reachable_from(&[1, 2, 2, 3, 4, 0]));
+}
+
+#[test]
+fn keeps_regions_without_a_blank_in_place() {
+    // the blocked middle column splits the board, only the left column has a blank
+    let board: Board = Board::new(3, 2).with_blocked(&[1, 4]);
+    let goal: LabelledGoal<6> = LabelledGoal::new(board, [1, 0, 2, 0, 0, 3]).unwrap();
+
+    assert!(goal.is_reachable_from(&[0, 0, 2, 1, 0, 3]));
+    assert!(!goal.is_reachable_from(&[1, 0, 3, 0, 0, 2]));
+    assert_eq!(LabelledAStar::with_goal(goal.clone()).solve(&[0, 0, 2, 1, 0, 3]).unwrap().moves, vec![LabelledMove { position: 0, direction: Direction::Down }]);
+    assert_eq!(goal.apply(&mut [1, 0, 2, 0, 0, 3], LabelledMove { position: 1, direction: Direction::Left }), Err(LabelledMove { position: 1, direction: Direction::Left }));
+}
+
+#[test]
+fn rejects_invalid_labels() {
+    assert_eq!(LabelledGoal::<4>::new(Board::square(2), [1, 1, 2, 2]), Err(LabelledGoalError::NoBlank));
+    assert_eq!(LabelledGoal::<4>::new(Board::square(2), [1, -1, 2, 0]), Err(LabelledGoalError::InvalidLabel(-1)));
+    assert_eq!(parse_labels::<4>("1 1 _ x"), Err(ParseStateError::InvalidTile("x".to_string())));
+    assert_eq!(parse_labels::<4>("1 1 _"), Err(ParseStateError::WrongTileCount(3, 4)));
+}
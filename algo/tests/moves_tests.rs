@@ -0,0 +1,31 @@
+use algo::{compose_moves, invert_moves, replay_moves, verify_solution, AStar, Board, Goal, MoveNotation, Solve, SolvedState, VerifyError};
+
+#[test]
+fn notation_round_trips_in_both_conventions() {
+    let moves = MoveNotation::Blank.parse("LURDDR").unwrap();
+
+    assert_eq!(MoveNotation::Blank.format(&moves), "LURDDR");
+    assert_eq!(MoveNotation::Tile.parse(&MoveNotation::Tile.format(&moves)).unwrap(), moves);
+}
+
+#[test]
+fn inverted_moves_return_to_the_start() {
+    let start: [i8; 9] = [5, 2, 3, 1, 8, 4, 6, 0, 7];
+    let moves = MoveNotation::Blank.parse("LDRRUU").unwrap();
+
+    let scrambled: [i8; 9] = replay_moves(&Board::default(), &start, &moves).unwrap();
+
+    assert_eq!(replay_moves(&Board::default(), &scrambled, &invert_moves(&moves)).unwrap(), start);
+    assert!(compose_moves(&moves, &invert_moves(&moves)).is_empty());
+}
+
+#[test]
+fn verify_solution_checks_a_star() {
+    let mut start: [i8; 9] = [5, 2, 3, 1, 8, 4, 6, 0, 7];
+    let solved_state: SolvedState = AStar::new().solve(&mut start).unwrap();
+
+    assert_eq!(verify_solution(&start, &solved_state, &Goal::default()), Ok(()));
+
+    let wrong_state = SolvedState::new(solved_state.puzzle_state, MoveNotation::Blank.parse("DD").unwrap());
+    assert!(matches!(verify_solution(&start, &wrong_state, &Goal::default()), Err(VerifyError::IllegalMove(illegal_move)) if illegal_move.index == 1));
+}
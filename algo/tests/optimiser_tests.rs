@@ -0,0 +1,74 @@
+use algo::{replay_moves, AStar, Board, Direction, Goal, MoveNotation, OptimisedMoves, Solve, SolutionOptimiser, WindowSearch};
+
+/// Walks the movable piece around the board at random without undoing moves, taking plenty of detours.
+fn wander<const N: usize>(board: &Board, puzzle_state: &[i8; N], length: usize) -> Vec<Direction> {
+    let mut moves: Vec<Direction> = Vec::new();
+    let mut state: [i8; N] = *puzzle_state;
+    let mut random: u64 = 7;
+    for _ in 0..length {
+        random = random.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let direction: Direction = (0..4)
+            .map(|offset| Direction::MOVES[((random >> 33) as usize + offset) % 4])
+            .filter(|direction| moves.last().is_none_or(|last| *last != direction.opposite()))
+            .find(|direction| replay_moves(board, &state, &[*direction]).is_ok())
+            .unwrap();
+        state = replay_moves(board, &state, &[direction]).unwrap();
+        moves.push(direction);
+    }
+    moves
+}
+
+#[test]
+fn shortened_moves_end_in_the_same_puzzle_state() {
+    let board: Board = Board::new(3, 2);
+    let start: [i8; 6] = [0, 1, 2, 3, 4, 5];
+    let moves: Vec<Direction> = wander(&board, &start, 60);
+    let end: [i8; 6] = replay_moves(&board, &start, &moves).unwrap();
+
+    for window_search in [WindowSearch::AStar { max_expanded: 10_000 }, WindowSearch::DistanceTable { max_expanded: 10_000 }] {
+        let optimised: OptimisedMoves = SolutionOptimiser::new(board).with_window(8).with_window_search(window_search).optimise(&start, &moves).unwrap();
+
+        assert_eq!(replay_moves(&board, &start, &optimised.moves).unwrap(), end);
+        assert_eq!(optimised.original_length, 60);
+        assert_eq!(optimised.saved(), 60 - optimised.moves.len());
+        assert!(optimised.saved() > 0 && optimised.replaced_windows > 0);
+    }
+}
+
+#[test]
+fn a_window_over_all_moves_finds_the_shortest_moves() {
+    let board: Board = Board::default();
+    let start: [i8; 9] = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+    let moves: Vec<Direction> = wander(&board, &start, 30);
+    let end: [i8; 9] = replay_moves(&board, &start, &moves).unwrap();
+    let shortest: usize = AStar::with_goal(Goal::new(board, end).unwrap()).solve(&mut start.clone()).unwrap().moves.len();
+
+    let optimised: OptimisedMoves = SolutionOptimiser::new(board).with_window(30).with_window_search(WindowSearch::DistanceTable { max_expanded: SolutionOptimiser::<9>::DEFAULT_MAX_EXPANDED }).optimise(&start, &moves).unwrap();
+
+    assert_eq!(optimised.moves.len(), shortest);
+}
+
+#[test]
+fn puzzles_too_large_for_a_table_fall_back_to_a_star_with_its_limit() {
+    let board: Board = Board::new(4, 3);
+    let start: [i8; 12] = std::array::from_fn(|tile| tile as i8);
+    let moves: Vec<Direction> = wander(&board, &start, 60);
+    let optimise = |max_expanded: u64| SolutionOptimiser::new(board).with_window(8).with_window_search(WindowSearch::DistanceTable { max_expanded }).optimise(&start, &moves).unwrap();
+
+    assert_eq!(optimise(1).replaced_windows, 0);
+    assert!(optimise(10_000).saved() > 0);
+}
+
+#[test]
+fn rejects_moves_that_leave_the_board() {
+    let moves: Vec<Direction> = MoveNotation::Blank.parse("UUU").unwrap();
+    let illegal_move = SolutionOptimiser::new(Board::default()).optimise(&[0, 1, 2, 3, 4, 5, 6, 7, 8], &moves).unwrap_err();
+
+    assert_eq!(illegal_move.index, 2);
+}
+
+#[test]
+#[should_panic(expected = "a window needs at least two moves")]
+fn windows_need_two_moves() {
+    SolutionOptimiser::<9>::new(Board::default()).with_window(1);
+}
@@ -0,0 +1,61 @@
+use std::time::{Duration, Instant};
+
+use algo::{replay_moves, AStar, Board, Direction, IdaStar, Portfolio, SearchLimits, Solve, SolveError, SolvedState};
+
+/// Answers with a detour right away, like a greedy solver would.
+struct Detour {
+    moves: Vec<Direction>,
+}
+
+impl Solve for Detour {
+    fn solve(&mut self, puzzle_state: &mut [i8; 9]) -> Result<SolvedState, SolveError> {
+        let final_state: [i8; 9] = replay_moves(&Board::default(), puzzle_state, &self.moves).unwrap();
+        Ok(SolvedState::new(final_state, self.moves.clone()))
+    }
+}
+
+/// One of the puzzle states of the 8-puzzle that need the most moves.
+fn hard_puzzle() -> [i8; 9] {
+    [7, 5, 6, 1, 4, 3, 2, 8, 0]
+}
+
+#[test]
+fn the_first_optimal_solver_wins() {
+    let mut portfolio: Portfolio = Portfolio::new()
+        .with_solver("astar", |limits| AStar::new().with_limits(limits))
+        .with_solver("idastar", |limits| IdaStar::new().with_limits(limits));
+
+    let solved_state = portfolio.solve(&mut hard_puzzle()).unwrap();
+
+    assert_eq!(solved_state.moves.len(), 31);
+    assert!(["astar", "idastar"].contains(&portfolio.winner().unwrap()));
+}
+
+#[test]
+fn inexact_answers_wait_for_an_optimal_one() {
+    let mut portfolio: Portfolio = Portfolio::new()
+        .with_inexact_solver("detour", |_| Detour { moves: vec![Direction::Right, Direction::Down, Direction::Up, Direction::Down] })
+        .with_solver("astar", |limits| AStar::new().with_limits(limits));
+
+    let solved_state = portfolio.solve(&mut [0, 1, 2, 3, 8, 4, 6, 7, 5]).unwrap();
+
+    assert_eq!(portfolio.winner(), Some("astar"));
+    assert_eq!(solved_state.moves, vec![Direction::Right, Direction::Down]);
+}
+
+#[test]
+fn the_deadline_cancels_the_losers() {
+    let started: Instant = Instant::now();
+    let mut portfolio: Portfolio = Portfolio::new()
+        .with_solver("idastar", |limits| IdaStar::new().with_heuristic(algo::Heuristic::MisplacedTiles).with_limits(limits))
+        .with_deadline(Duration::from_millis(20));
+
+    let result = portfolio.solve(&mut hard_puzzle());
+
+    assert!(matches!(result, Err(SolveError::LimitReached(_))));
+    assert_eq!(portfolio.winner(), None);
+    assert!(started.elapsed() < Duration::from_secs(5));
+    let limits: SearchLimits = SearchLimits { max_expanded: Some(1), ..SearchLimits::none() };
+    let mut portfolio: Portfolio = Portfolio::new().with_solver("astar", move |_| AStar::new().with_limits(limits.clone()));
+    assert!(matches!(portfolio.solve(&mut hard_puzzle()), Err(SolveError::LimitReached(_))));
+}
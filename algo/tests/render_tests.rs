@@ -0,0 +1,46 @@
+use algo::{replay_steps, AStar, Board, Goal, Renderer, Solve, SolvedState};
+
+/// Gets the colour of a pixel of a 96 by 64 PPM image.
+fn pixel(ppm: &[u8], x: usize, y: usize) -> [u8; 3] {
+    let offset: usize = "P6\n96 64\n255\n".len() + (y * 96 + x) * 3;
+    [ppm[offset], ppm[offset + 1], ppm[offset + 2]]
+}
+
+#[test]
+fn draws_tiles_and_leaves_the_movable_piece_empty() {
+    let board: Board = Board::new(3, 2).with_blocked(&[2]);
+    let renderer: Renderer = Renderer::new(board).with_tile_size(32);
+    let puzzle_state: [i8; 6] = [5, 0, 1, 2, 3, 4];
+    let ppm: Vec<u8> = renderer.ppm(&puzzle_state);
+
+    assert_eq!(renderer.size(), (96, 64));
+    assert!(ppm.starts_with(b"P6\n96 64\n255\n"));
+    let background: [u8; 3] = pixel(&ppm, 16, 16);
+    let tile: [u8; 3] = pixel(&ppm, 34, 34);
+    assert_ne!(background, tile);
+    assert_eq!(pixel(&ppm, 0, 0), background);
+    assert_ne!(pixel(&ppm, 70, 2), background);
+    // the label of tile 1 is drawn in the middle of the tile
+    assert!((40..56).any(|x| pixel(&ppm, x, 16) != tile));
+
+    let svg: String = renderer.svg(&puzzle_state);
+    assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="96" height="64""#));
+    assert_eq!(svg.matches("</text>").count(), 5);
+    assert_eq!(svg.matches(r##"fill="#111111""##).count(), 1);
+}
+
+#[test]
+fn draws_every_step_on_the_sprite_sheet() {
+    let start: [i8; 9] = [0, 1, 2, 3, 4, 5, 8, 6, 7];
+    let solved_state: SolvedState = AStar::new().solve(&mut start.clone()).unwrap();
+    let steps: Vec<[i8; 9]> = replay_steps(&Board::default(), &start, &solved_state.moves).unwrap();
+
+    assert_eq!(steps.len(), 3);
+    assert!(Goal::default().is_reached(steps.last().unwrap()));
+
+    let sheet: String = Renderer::new(Board::default()).with_tile_size(20).sprite_sheet(&steps, &solved_state.moves, 2);
+    assert!(sheet.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="140" height="160""#));
+    assert_eq!(sheet.matches("<g transform").count(), 3);
+    assert!(sheet.contains(r#"<g transform="translate(0,80)">"#));
+    assert!(sheet.contains(">1 R</text>") && sheet.contains(">2 R</text>"));
+}
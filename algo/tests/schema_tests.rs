@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use algo::{AStar, Board, Direction, SearchStats, Solve, SolvedState, Topology};
+use serde_json::json;
+
+#[test]
+fn solved_states_follow_the_schema() {
+    let mut start: [i8; 9] = [0, 1, 2, 3, 4, 5, 6, 8, 7];
+    let solved_state: SolvedState = AStar::new().solve(&mut start).unwrap();
+    let json = serde_json::to_value(&solved_state).unwrap();
+
+    assert_eq!(json["puzzle_state"], json!([1, 2, 3, 4, 5, 6, 7, 8, 0]));
+    assert_eq!(json["moves"], json!("R"));
+    assert_eq!(json["stats"]["expanded"], json!(solved_state.stats.expanded));
+    assert!(json["stats"]["elapsed_ms"].is_f64());
+    assert_eq!(serde_json::from_value::<SolvedState>(json).unwrap().moves, solved_state.moves);
+}
+
+#[test]
+fn values_read_back_the_same() {
+    let solved_state: SolvedState<16> = SolvedState {
+        puzzle_state: core::array::from_fn(|i: usize| i as i8),
+        moves: vec![Direction::Left, Direction::Up, Direction::Right, Direction::Down],
+        stats: SearchStats { expanded: 3, generated: 7, elapsed: Duration::from_millis(250) },
+    };
+    let text: String = serde_json::to_string(&solved_state).unwrap();
+
+    assert_eq!(serde_json::from_str::<SolvedState<16>>(&text).unwrap(), solved_state);
+    assert_eq!(serde_json::to_value(Board::new(4, 3)).unwrap(), json!({ "width": 4, "height": 3 }));
+    assert_eq!(serde_json::from_value::<Board>(json!({ "width": 2, "height": 5 })).unwrap(), Board::new(2, 5));
+
+    let blocked_torus: Board = Board::square(4).with_topology(Topology::Torus).with_blocked(&[10, 5]);
+    let json = serde_json::to_value(blocked_torus).unwrap();
+    assert_eq!(json, json!({ "width": 4, "height": 4, "topology": "torus", "blocked": [5, 10] }));
+    assert_eq!(serde_json::from_value::<Board>(json).unwrap(), blocked_torus);
+    assert_eq!(serde_json::to_value(Direction::Up).unwrap(), json!("U"));
+    assert_eq!(serde_json::from_value::<Vec<Direction>>(json!(["L", "d"])).unwrap(), vec![Direction::Left, Direction::Down]);
+}
+
+#[test]
+fn invalid_values_are_rejected() {
+    assert!(serde_json::from_value::<Board>(json!({ "width": 1, "height": 5 })).is_err());
+    assert!(serde_json::from_value::<Direction>(json!("X")).is_err());
+    let solved_state = |puzzle_state: serde_json::Value, moves: &str| json!({
+        "puzzle_state": puzzle_state,
+        "moves": moves,
+        "stats": { "expanded": 0, "generated": 0, "elapsed_ms": 0.0 },
+    });
+    assert!(serde_json::from_value::<SolvedState>(solved_state(json!([1, 2, 3, 4, 5, 6, 7, 8, 0]), "LX")).is_err());
+    assert!(serde_json::from_value::<SolvedState>(solved_state(json!([1, 2, 3, 4, 5, 6, 7, 8]), "L")).is_err());
+    assert!(serde_json::from_value::<SolvedState>(solved_state(json!([1, 2, 3, 4, 5, 6, 7, 8, 8]), "L")).is_err());
+    assert!(serde_json::from_value::<SolvedState>(solved_state(json!([1, 2, 3, 4, 5, 6, 7, 8, 0]), "L")).is_ok());
+}
@@ -0,0 +1,33 @@
+use algo::{check_solvability, create_puzzle_state, DistanceTable, Goal, ScrambleError, ScrambleMode, Scrambler};
+
+#[test]
+fn same_seed_gives_same_puzzles() {
+    let mut first: Scrambler = Scrambler::new(7);
+    let mut second: Scrambler = Scrambler::new(7);
+
+    for mode in [ScrambleMode::Shuffle, ScrambleMode::RandomWalk(30), ScrambleMode::ExactDistance(12)] {
+        assert_eq!(first.scramble(mode), second.scramble(mode));
+    }
+}
+
+#[test]
+fn scrambles_are_solvable_and_as_hard_as_asked() {
+    let goal: Goal = Goal::default();
+    let distance_table: DistanceTable = DistanceTable::new(&goal).unwrap();
+    let mut scrambler: Scrambler = Scrambler::new(1234);
+
+    for _ in 0..20 {
+        assert!(goal.is_reachable_from(&scrambler.scramble(ScrambleMode::Shuffle).unwrap()));
+        assert!(distance_table.distance(&scrambler.scramble(ScrambleMode::RandomWalk(9)).unwrap()).unwrap() <= 9);
+        assert_eq!(distance_table.distance(&scrambler.scramble(ScrambleMode::ExactDistance(20)).unwrap()), Some(20));
+    }
+    assert_eq!(scrambler.scramble(ScrambleMode::ExactDistance(32)), Err(ScrambleError::DistanceTooLarge(32, 31)));
+}
+
+#[test]
+fn created_puzzle_states_are_solvable() {
+    for _ in 0..20 {
+        assert!(check_solvability(&create_puzzle_state::<9>()));
+        assert!(check_solvability(&create_puzzle_state::<16>()));
+    }
+}
@@ -0,0 +1,48 @@
+use algo::{replay_moves, AStar, Board, Direction, Goal, Heuristic, ScrambleMode, Scrambler, SearchLimits, SmaStar, Solve, SolveError};
+
+#[test]
+fn stays_optimal_when_memory_runs_out() {
+    let mut scrambler: Scrambler = Scrambler::new(38);
+    for _ in 0..10 {
+        let start: [i8; 9] = scrambler.scramble(ScrambleMode::Shuffle).unwrap();
+        let optimal_length: usize = AStar::new().solve(&mut start.clone()).unwrap().moves.len();
+
+        for max_nodes in [60, 500, 100_000] {
+            let solved_state = SmaStar::new().with_max_nodes(max_nodes).solve(&mut start.clone()).unwrap();
+
+            assert_eq!(solved_state.moves.len(), optimal_length, "{:?} with {} nodes", start, max_nodes);
+            assert!(Goal::<9>::default().is_reached(&replay_moves(&Board::default(), &start, &solved_state.moves).unwrap()));
+        }
+    }
+}
+
+#[test]
+fn gives_up_when_the_path_does_not_fit() {
+    let mut start: [i8; 9] = [7, 5, 6, 1, 4, 3, 2, 8, 0];
+
+    assert!(matches!(SmaStar::new().with_max_nodes(20).solve(&mut start), Err(SolveError::LimitReached(_))));
+    let limits: SearchLimits = SearchLimits { max_expanded: Some(10), ..SearchLimits::none() };
+    assert!(matches!(SmaStar::new().with_heuristic(Heuristic::LinearConflict).with_limits(limits).solve(&mut start), Err(SolveError::LimitReached(stats)) if stats.expanded == 10));
+    assert_eq!(SmaStar::new().solve(&mut [1, 0, 2, 3, 4, 5, 6, 7, 8]).err(), Some(SolveError::Unsolvable));
+}
+
+#[test]
+fn finds_goals_with_the_movable_piece_in_a_dead_end() {
+    // the blocked position leaves the goal position of the movable piece a single neighbour
+    let board: Board = Board::square(3).with_blocked(&[5]);
+    let goal: Goal = Goal::blank_last(board);
+    let solved_state = SmaStar::with_goal(goal.clone()).solve(&mut [0, 1, 2, 3, 4, 5, 6, 8, 7]).unwrap();
+    assert_eq!(solved_state.moves, vec![Direction::Right]);
+
+    let mut scrambler: Scrambler = Scrambler::with_goal(5, goal.clone());
+    for _ in 0..5 {
+        let start: [i8; 9] = scrambler.scramble(ScrambleMode::Shuffle).unwrap();
+        let optimal_length: usize = AStar::with_goal(goal.clone()).solve(&mut start.clone()).unwrap().moves.len();
+        for max_nodes in [500, 100_000] {
+            let solved_state = SmaStar::with_goal(goal.clone()).with_max_nodes(max_nodes).solve(&mut start.clone()).unwrap();
+
+            assert_eq!(solved_state.moves.len(), optimal_length, "{:?} with {} nodes", start, max_nodes);
+            assert!(goal.is_reached(&replay_moves(&board, &start, &solved_state.moves).unwrap()));
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use algo::{replay_moves, AStar, Board, Goal, Solutions, Solve};
+
+#[test]
+fn optimal_solutions_are_distinct_and_as_long_as_a_star() {
+    let start: [i8; 9] = [5, 2, 3, 1, 8, 4, 6, 0, 7];
+    let optimal_length: usize = AStar::new().solve(&mut start.clone()).unwrap().moves.len();
+
+    let solutions = Solutions::optimal(start, &Goal::default()).unwrap().collect::<Vec<_>>();
+
+    assert_eq!(solutions.len(), 4);
+    for (i, solution) in solutions.iter().enumerate() {
+        assert_eq!(solution.cost, optimal_length);
+        assert_eq!(replay_moves(&Board::default(), &start, &solution.moves).unwrap(), *Goal::default().puzzle_state());
+        assert!(solutions[..i].iter().all(|other| other.moves != solution.moves));
+    }
+}
+
+#[test]
+fn shortest_solutions_never_get_cheaper() {
+    let start: [i8; 9] = [8, 3, 2, 1, 0, 4, 6, 7, 5];
+
+    let solutions = Solutions::shortest(start, &Goal::default()).unwrap().take(10).collect::<Vec<_>>();
+
+    assert_eq!(solutions.len(), 10);
+    assert!(solutions.windows(2).all(|pair| pair[0].cost <= pair[1].cost));
+    assert!(solutions.iter().all(|solution| replay_moves(&Board::default(), &start, &solution.moves).unwrap() == *Goal::default().puzzle_state()));
+}
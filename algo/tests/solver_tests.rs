@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use algo::{parse_puzzle_state, replay_moves, AStar, Board, Goal, Heuristic, IdaStar, PatternDatabase, SearchLimits, Solve, SolveError, TieBreaking};
+
+#[test]
+fn every_heuristic_finds_an_optimal_solution() {
+    let start: [i8; 9] = parse_puzzle_state("6 3 4 2 _ 5 7 1 8").unwrap();
+
+    for heuristic in Heuristic::ALL {
+        let a_star_moves = AStar::new().with_heuristic(heuristic).solve(&mut start.clone()).unwrap().moves;
+        let ida_star_moves = IdaStar::new().with_heuristic(heuristic).solve(&mut start.clone()).unwrap().moves;
+
+        assert_eq!(a_star_moves.len(), 22, "{}", heuristic);
+        assert_eq!(ida_star_moves.len(), 22, "{}", heuristic);
+    }
+}
+
+#[test]
+fn solves_rectangular_boards() {
+    let board: Board = Board::new(4, 2);
+    let goal: Goal<8> = Goal::blank_last(board);
+    let start: [i8; 8] = parse_puzzle_state("5 1 2 4 _ 6 3 7").unwrap();
+
+    let solved_state = IdaStar::with_goal(goal.clone()).with_heuristic(Heuristic::LinearConflict).solve(&mut start.clone()).unwrap();
+
+    assert!(goal.is_reached(&replay_moves(&board, &start, &solved_state.moves).unwrap()));
+}
+
+#[test]
+fn gives_up_when_the_limit_is_reached() {
+    let mut start: [i8; 9] = parse_puzzle_state("8 6 7 2 5 4 3 _ 1").unwrap();
+    let limits: SearchLimits = SearchLimits { max_expanded: Some(10), ..SearchLimits::none() };
+
+    assert!(matches!(AStar::new().with_limits(limits.clone()).solve(&mut start), Err(SolveError::LimitReached(stats)) if stats.expanded == 10));
+    assert!(matches!(IdaStar::new().with_limits(limits).solve(&mut start), Err(SolveError::LimitReached(_))));
+}
+
+#[test]
+fn every_tie_breaking_stays_optimal() {
+    let board: Board = Board::square(4);
+    let start: [i8; 16] = parse_puzzle_state("1 6 _ 4 5 11 7 3 15 14 8 2 10 9 13 12").unwrap();
+
+    let expanded = TieBreaking::ALL.map(|tie_breaking| {
+        assert_eq!(tie_breaking.name().parse::<TieBreaking>(), Ok(tie_breaking));
+        let solved_state = AStar::with_goal(Goal::blank_last(board)).with_tie_breaking(tie_breaking).solve(&mut start.clone()).unwrap();
+        assert_eq!(solved_state.moves.len(), 36, "{}", tie_breaking);
+        solved_state.stats.expanded
+    });
+
+    // preferring the longest paths among equal values heads straight for the goal on the last iso-value layer
+    assert!(expanded[0] < expanded[3]);
+}
+
+#[test]
+fn a_pattern_database_keeps_ida_star_optimal_with_fewer_nodes() {
+    let board: Board = Board::square(4);
+    let goal: Goal<16> = Goal::blank_last(board);
+    let pattern_database: Arc<PatternDatabase<16>> = Arc::new(PatternDatabase::new(&goal, &[0, 1, 2, 3]).unwrap());
+
+    for text in ["1 6 _ 4 5 11 7 3 15 14 8 2 10 9 13 12", "5 1 3 4 _ 2 6 8 9 10 7 11 13 14 15 12", "2 5 3 4 1 10 6 8 9 7 _ 12 13 14 11 15"] {
+        let start: [i8; 16] = parse_puzzle_state(text).unwrap();
+        let plain = IdaStar::with_goal(goal.clone()).solve(&mut start.clone()).unwrap();
+        let with_database = IdaStar::with_goal(goal.clone()).with_pattern_database(pattern_database.clone()).solve(&mut start.clone()).unwrap();
+
+        assert_eq!(with_database.moves.len(), plain.moves.len(), "{}", text);
+        assert!(with_database.stats.expanded <= plain.stats.expanded, "{}", text);
+    }
+}
@@ -0,0 +1,82 @@
+use algo::{AStar, Board, DistanceTable, Goal, PatternDatabase, Scrambler, ScrambleMode, Solve, Symmetry};
+
+#[test]
+fn mirrors_states_onto_states_with_the_same_distance() {
+    let goal: Goal = Goal::default();
+    let symmetry: Symmetry = Symmetry::diagonal(&goal).unwrap();
+    let mut scrambler: Scrambler = Scrambler::new(42);
+    let mut solver: AStar = AStar::new();
+    for _ in 0..20 {
+        let puzzle_state: [i8; 9] = scrambler.scramble(ScrambleMode::Shuffle).unwrap();
+        let mirrored_state: [i8; 9] = symmetry.mirror(&puzzle_state);
+        let solved_state = solver.solve(&mut puzzle_state.clone()).unwrap();
+        let mirrored_solved_state = solver.solve(&mut mirrored_state.clone()).unwrap();
+
+        assert_eq!(symmetry.mirror(&mirrored_state), puzzle_state);
+        assert_eq!(solved_state.moves.len(), mirrored_solved_state.moves.len());
+    }
+
+    assert!(Symmetry::diagonal(&Goal::<16>::spiral(Board::square(4))).is_none());
+    assert!(Symmetry::diagonal(&Goal::<6>::blank_last(Board::new(3, 2))).is_none());
+    assert!(Symmetry::diagonal(&Goal::<16>::blank_first(Board::square(4))).is_some());
+}
+
+#[test]
+fn distance_tables_keep_only_canonical_states() {
+    let distance_table: DistanceTable = DistanceTable::new(&Goal::default()).unwrap();
+    let spiral_table: DistanceTable<9> = DistanceTable::new(&Goal::spiral(Board::square(3))).unwrap();
+    let rectangle_table: DistanceTable<6> = DistanceTable::new(&Goal::blank_last(Board::new(3, 2))).unwrap();
+
+    assert!(distance_table.symmetry().is_some());
+    assert_eq!(distance_table.size(), 362_880 * 2 / 3);
+    assert_eq!(rectangle_table.size(), 720);
+    assert_eq!(spiral_table.size(), 362_880 * 2 / 3);
+
+    // the published number of eight-puzzle states at every distance
+    let layer_sizes: Vec<usize> = (0..=distance_table.max_distance()).map(|distance| distance_table.states_at(distance).count()).collect::<Vec<_>>();
+    assert_eq!(
+        layer_sizes,
+        [
+            1, 2, 4, 8, 16, 20, 39, 62, 116, 152, 286, 396, 748, 1024, 1893, 2512, 4485, 5638, 9529, 10878, 16993, 17110, 23952, 20224, 24047, 15578,
+            14560, 6274, 3910, 760, 221, 2
+        ]
+    );
+    let mut solver: AStar = AStar::new();
+    for puzzle_state in distance_table.states_at(24).step_by(997) {
+        assert_eq!(solver.solve(&mut puzzle_state.clone()).unwrap().moves.len(), 24);
+    }
+}
+
+#[test]
+fn mirrored_pattern_lookups_are_stronger_and_stay_admissible() {
+    let goal: Goal = Goal::default();
+    let distance_table: DistanceTable = DistanceTable::new(&goal).unwrap();
+    let pattern_database: PatternDatabase = PatternDatabase::new(&goal, &[0, 1, 2]).unwrap();
+    let (mut lookups, mut estimates, mut higher): (i32, i32, usize) = (0, 0, 0);
+    for distance in 0..=distance_table.max_distance() {
+        for puzzle_state in distance_table.states_at(distance).step_by(13) {
+            let (lookup, estimate) = (pattern_database.lookup(&puzzle_state), pattern_database.estimate(&puzzle_state));
+            assert!(estimate <= distance as i32, "{:?} is {} moves away, estimated {}", puzzle_state, distance, estimate);
+            assert!(estimate >= lookup, "{:?} is estimated {} below its lookup {}", puzzle_state, estimate, lookup);
+            higher += (estimate > lookup) as usize;
+            lookups += lookup;
+            estimates += estimate;
+        }
+    }
+    assert!(higher > 0);
+    assert!(estimates as f64 > lookups as f64 * 1.05, "{} against {}", estimates, lookups);
+
+    let fifteen_goal: Goal<16> = Goal::blank_last(Board::square(4));
+    let fifteen_database: PatternDatabase<16> = PatternDatabase::new(&fifteen_goal, &[0, 1, 2, 3]).unwrap();
+    let mut scrambler: Scrambler<16> = Scrambler::with_goal(7, fifteen_goal);
+    let (mut lookups, mut estimates): (i32, i32) = (0, 0);
+    for _ in 0..200 {
+        let puzzle_state: [i8; 16] = scrambler.scramble(ScrambleMode::Shuffle).unwrap();
+        let (lookup, estimate) = (fifteen_database.lookup(&puzzle_state), fifteen_database.estimate(&puzzle_state));
+        assert!(estimate >= lookup);
+        lookups += lookup;
+        estimates += estimate;
+    }
+    assert_eq!(fifteen_database.size(), 16 * 15 * 14 * 13 * 12);
+    assert!(estimates as f64 > lookups as f64 * 1.05, "{} against {}", estimates, lookups);
+}
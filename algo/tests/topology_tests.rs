@@ -0,0 +1,95 @@
+use algo::{parse_puzzle_state, replay_moves, AStar, BidirectionalAStar, Board, DistanceTable, Goal, GoalError, Heuristic, IdaStar, ScrambleMode, Scrambler, SmaStar, Solutions, Solve, SolveError, Topology};
+
+/// Checks the reachability rules against every state a distance table reaches from the goal.
+fn assert_reachability<const N: usize>(board: Board, reachable: usize) {
+    let goal: Goal<N> = Goal::blank_last(board);
+    let distance_table: DistanceTable<N> = DistanceTable::new(&goal).unwrap();
+    let reached: usize = (0..=distance_table.max_distance()).map(|distance| distance_table.states_at(distance).count()).sum();
+    assert_eq!(reached, reachable, "{}", board);
+
+    let mut scrambler: Scrambler<N> = Scrambler::with_goal(3, goal.clone());
+    for _ in 0..500 {
+        let puzzle_state: [i8; N] = scrambler.scramble(ScrambleMode::Shuffle).unwrap();
+        for puzzle_state in [puzzle_state, swap_first_tiles(puzzle_state)] {
+            assert_eq!(goal.is_reachable_from(&puzzle_state), distance_table.distance(&puzzle_state).is_some(), "{:?} on {}", puzzle_state, board);
+        }
+    }
+}
+
+fn swap_first_tiles<const N: usize>(mut puzzle_state: [i8; N]) -> [i8; N] {
+    let tiles: Vec<usize> = (0..N).filter(|position| puzzle_state[*position] != N as i8 - 1).take(2).collect::<Vec<_>>();
+    puzzle_state.swap(tiles[0], tiles[1]);
+    puzzle_state
+}
+
+#[test]
+fn wrapping_around_an_odd_number_of_positions_reaches_every_state() {
+    assert_reachability::<9>(Board::square(3).with_topology(Topology::Torus), 362_880);
+    assert_reachability::<6>(Board::new(3, 2).with_topology(Topology::Cylinder), 720);
+    assert_reachability::<8>(Board::new(4, 2).with_topology(Topology::Cylinder), 20_160);
+    assert_reachability::<8>(Board::new(2, 4).with_topology(Topology::Torus), 20_160);
+    assert_reachability::<9>(Board::square(3).with_blocked(&[0]), 20_160);
+}
+
+#[test]
+fn regions_where_tiles_cant_pass_each_other_reach_fewer_states() {
+    // a cycle around the blocked centre only rotates the tiles
+    assert_reachability::<9>(Board::square(3).with_blocked(&[4]), 56);
+    // positions 3 and 5 split the region, and 5 still does once 0 is blocked too
+    assert_reachability::<9>(Board::square(3).with_blocked(&[1]), 480);
+    assert_reachability::<9>(Board::square(3).with_blocked(&[0, 1]), 420);
+
+    // swapping two pairs of tiles keeps the parity, but not the order around the cycle
+    let goal: Goal = Goal::blank_last(Board::square(3).with_blocked(&[4]));
+    let start: [i8; 9] = parse_puzzle_state("2 1 6 4 5 3 7 8 _").unwrap();
+    assert!(!goal.is_reachable_from(&start));
+    assert_eq!(IdaStar::with_goal(goal.clone()).solve(&mut start.clone()), Err(SolveError::Unsolvable));
+    assert!(matches!(Solutions::shortest(start, &goal), Err(SolveError::Unsolvable)));
+    assert_eq!(goal.max_reachable_states(), 20_160);
+}
+
+#[test]
+fn solvers_stay_optimal_on_every_topology() {
+    // on the last board the movable piece can only leave its goal position one way
+    for board in [Board::square(3).with_topology(Topology::Torus), Board::square(3).with_topology(Topology::Cylinder), Board::square(3).with_blocked(&[2]), Board::square(3).with_blocked(&[5])] {
+        let goal: Goal = Goal::blank_last(board);
+        let distance_table: DistanceTable = DistanceTable::new(&goal).unwrap();
+        let mut scrambler: Scrambler = Scrambler::with_goal(11, goal.clone());
+        // one move away, the last move puts the movable piece in its goal position
+        let starts: Vec<[i8; 9]> = (0..5).map(|_| scrambler.scramble(ScrambleMode::Shuffle).unwrap()).chain([[0, 1, 2, 3, 4, 5, 6, 8, 7]]).collect::<Vec<_>>();
+        for start in starts {
+            let optimal_length: usize = distance_table.distance(&start).unwrap();
+            let solvers: Vec<Box<dyn Solve<9>>> = vec![
+                Box::new(AStar::with_goal(goal.clone())),
+                Box::new(IdaStar::with_goal(goal.clone()).with_heuristic(Heuristic::LinearConflict)),
+                Box::new(BidirectionalAStar::with_goal(goal.clone())),
+                Box::new(SmaStar::with_goal(goal.clone())),
+            ];
+            for mut solver in solvers {
+                let solved_state = solver.solve(&mut start.clone()).unwrap();
+
+                assert_eq!(solved_state.moves.len(), optimal_length, "{:?} on {}", start, board);
+                assert!(goal.is_reached(&replay_moves(&board, &start, &solved_state.moves).unwrap()));
+            }
+        }
+    }
+}
+
+#[test]
+fn writes_and_reads_topologies() {
+    let torus: Board = "4x4:torus".parse().unwrap();
+    let blocked: Board = "3x3:cylinder:0,4".parse().unwrap();
+
+    assert_eq!(torus, Board::square(4).with_topology(Topology::Torus));
+    assert_eq!(blocked.blocked_positions(), vec![0, 4]);
+    assert_eq!(blocked.to_string(), "3x3:cylinder:0,4");
+    assert_eq!(Board::square(3).to_string(), "3x3");
+    assert!("3x3:sphere".parse::<Board>().is_err());
+    assert!("3x3:flat:9".parse::<Board>().is_err());
+
+    assert_eq!(serde_json::to_string(&Board::square(3)).unwrap(), r#"{"width":3,"height":3}"#);
+    assert_eq!(serde_json::to_string(&blocked).unwrap(), r#"{"width":3,"height":3,"topology":"cylinder","blocked":[0,4]}"#);
+    assert_eq!(serde_json::from_str::<Board>(&serde_json::to_string(&torus).unwrap()).unwrap(), torus);
+
+    assert_eq!(Goal::<9>::new(Board::square(3).with_blocked(&[8]), [0, 1, 2, 3, 4, 5, 6, 7, 8]), Err(GoalError::BlockedMovablePiece(8)));
+}